@@ -4,11 +4,15 @@ extern crate rusted_tiles;
 use rusted_arena::game::component::body::Body::*;
 use rusted_arena::game::component::body::*;
 use rusted_arena::game::component::graphic::Graphic;
+use rusted_arena::game::component::locomotion::Locomotion;
+use rusted_arena::game::component::occupancy::Occupancy;
 use rusted_arena::game::map::builder::TileMapBuilder;
+use rusted_arena::game::map::mobility::{Mobility, MovementMode};
 use rusted_arena::game::map::*;
+use rusted_arena::game::system::displacement::move_entity;
 use rusted_arena::game::system::movement::*;
 use rusted_arena::game::system::rendering::render_entities;
-use rusted_arena::utils::ecs::storage::ComponentStorage;
+use rusted_arena::game::system::snake::{grow_snake, shrink_snake};
 use rusted_arena::utils::ecs::ECS;
 use rusted_tiles::math::color::*;
 use rusted_tiles::math::get_index;
@@ -31,7 +35,7 @@ impl App for MapApp {
         self.tile_renderer.clear();
         self.map.render(&mut self.tile_renderer);
 
-        render_entities(&mut self.ecs, &mut self.tile_renderer, self.map.get_size());
+        render_entities(&mut self.ecs, &mut self.tile_renderer, &self.map);
 
         renderer.start(BLACK);
         self.tile_renderer.render(renderer);
@@ -52,6 +56,8 @@ impl App for MapApp {
             VirtualKeyCode::Key1 => self.current_body = 0,
             VirtualKeyCode::Key2 => self.current_body = 1,
             VirtualKeyCode::Key3 => self.current_body = 2,
+            VirtualKeyCode::G => grow_snake(&mut self.ecs, self.current_body, 5),
+            VirtualKeyCode::H => self.try_shrink(),
             _ => (),
         }
     }
@@ -67,13 +73,17 @@ impl MapApp {
         }
     }
 
+    fn try_shrink(&mut self) {
+        if let Err(error) = shrink_snake(&mut self.ecs, &mut self.map, self.current_body, 5) {
+            println!("Shrinking failed: {}", error)
+        }
+    }
+
     fn try_move(&mut self, dir: Direction) {
-        let body_storage = self.ecs.get_storage_mgr_mut().get_mut::<Body>();
+        let result = move_entity(&mut self.ecs, &mut self.map, self.current_body, dir);
 
-        if let Some(body) = body_storage.get_mut(self.current_body) {
-            if !move_body(&mut self.map, self.current_body, body, dir) {
-                println!("Neighbor for {:?} is blocked!", dir)
-            }
+        if !result.is_moved() {
+            println!("Moving {:?} failed: {:?}", dir, result)
         }
     }
 }
@@ -89,6 +99,7 @@ fn main() {
             TileType::Wall,
         )
         .set_tile(Point { x: 20, y: 15 }, TileType::Floor)
+        .add_rectangle(Point { x: 5, y: 3 }, Point { x: 3, y: 24 }, TileType::Chasm)
         .build();
 
     let mut window = GliumWindow::new("Map Example", size, tile_size);
@@ -97,18 +108,23 @@ fn main() {
 
     ecs.get_storage_mgr_mut().register::<Body>();
     ecs.get_storage_mgr_mut().register::<Graphic>();
+    ecs.get_storage_mgr_mut().register::<Locomotion>();
+    ecs.get_storage_mgr_mut().register::<Occupancy>();
 
     ecs.create_entity()
         .with(Simple(get_index(10, 10, size)))
         .with(Graphic::ascii(b'@', WHITE));
     ecs.create_entity()
         .with(Big(get_index(10, 20, size), 5))
+        .with(Locomotion {
+            mobility: Mobility::new(&[MovementMode::Walk, MovementMode::Fly]),
+        })
         .with(Graphic::ascii(b'D', RED));
     ecs.create_entity()
         .with(Snake(vec![get_index(35, 5, size); 25]))
         .with(Graphic::two_ascii(b'S', CYAN, b'S', BLUE));
 
-    add_all_to_map(&mut ecs, &mut tile_map);
+    add_all_to_map(&mut ecs, &mut tile_map).expect("Could not add all entities to the map!");
 
     let app = Rc::new(RefCell::new(MapApp::new(
         ecs,
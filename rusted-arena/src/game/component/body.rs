@@ -1,11 +1,61 @@
+use crate::game::map::Direction;
 use crate::utils::ecs::component::Component;
 use crate::utils::ecs::storage::ComponentMap;
+use rusted_tiles::math::point::Point;
+use rusted_tiles::math::{get_index, get_point};
 
 #[derive(PartialEq, Clone, Debug)]
 pub enum Body {
     Simple(usize),
     Big(usize, u32),
     Snake(Vec<usize>),
+    Shaped(usize, Shape),
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Shape {
+    pub offsets: Vec<(i32, i32)>,
+    pub facing: Direction,
+    pub rotates: bool,
+}
+
+impl Shape {
+    pub fn new(offsets: Vec<(i32, i32)>) -> Shape {
+        Shape {
+            offsets,
+            facing: Direction::North,
+            rotates: false,
+        }
+    }
+
+    pub fn rectangle(size: Point) -> Shape {
+        let mut offsets = Vec::new();
+
+        for y in 0..size.y {
+            for x in 0..size.x {
+                offsets.push((x as i32, y as i32));
+            }
+        }
+
+        Shape::new(offsets)
+    }
+
+    pub fn rotating(mut self) -> Shape {
+        self.rotates = true;
+        self
+    }
+
+    pub fn get_offsets(&self) -> Vec<(i32, i32)> {
+        self.offsets
+            .iter()
+            .map(|&(x, y)| match self.facing {
+                Direction::East => (y, -x),
+                Direction::South => (-x, -y),
+                Direction::West => (-y, x),
+                _ => (x, y),
+            })
+            .collect()
+    }
 }
 
 impl Component for Body {
@@ -21,6 +71,115 @@ pub fn get_position(body: &Body) -> usize {
         Body::Simple(index) => *index,
         Body::Big(index, _) => *index,
         Body::Snake(indices) => indices[0],
+        Body::Shaped(index, _) => *index,
+    }
+}
+
+pub fn get_indices(body: &Body, map_size: Point) -> Vec<usize> {
+    match body {
+        Body::Simple(index) => vec![*index],
+        Body::Big(index, size) => {
+            let pos = get_point(*index, map_size);
+            let mut indices = Vec::new();
+
+            for y in pos.y..(pos.y + size) {
+                for x in pos.x..(pos.x + size) {
+                    indices.push(get_index(x, y, map_size));
+                }
+            }
+
+            indices
+        }
+        Body::Snake(indices) => {
+            let mut unique = Vec::new();
+
+            for index in indices {
+                if !unique.contains(index) {
+                    unique.push(*index);
+                }
+            }
+
+            unique
+        }
+        Body::Shaped(index, shape) => get_shape_indices(*index, shape, map_size)
+            .flatten()
+            .collect(),
+    }
+}
+
+pub fn get_checked_indices(body: &Body, map_size: Point) -> Option<Vec<usize>> {
+    match body {
+        Body::Big(index, size) => {
+            let pos = get_point(*index, map_size);
+
+            if pos.x + size > map_size.x || pos.y + size > map_size.y {
+                return None;
+            }
+
+            Some(get_indices(body, map_size))
+        }
+        Body::Shaped(index, shape) => get_shape_indices(*index, shape, map_size).collect(),
+        _ => Some(get_indices(body, map_size)),
+    }
+}
+
+fn get_shape_indices<'a>(
+    index: usize,
+    shape: &'a Shape,
+    map_size: Point,
+) -> impl Iterator<Item = Option<usize>> + 'a {
+    let pos = get_point(index, map_size);
+
+    shape
+        .get_offsets()
+        .into_iter()
+        .map(move |(delta_x, delta_y)| {
+            let x = pos.x as i32 + delta_x;
+            let y = pos.y as i32 + delta_y;
+
+            if x < 0 || x >= map_size.x as i32 || y < 0 || y >= map_size.y as i32 {
+                None
+            } else {
+                Some(get_index(x as u32, y as u32, map_size))
+            }
+        })
+}
+
+pub fn set_position(body: &mut Body, new_index: usize) {
+    match body {
+        Body::Simple(index) => *index = new_index,
+        Body::Big(index, _) => *index = new_index,
+        Body::Shaped(index, _) => *index = new_index,
+        Body::Snake(indices) => {
+            for index in indices.iter_mut() {
+                *index = new_index;
+            }
+        }
+    }
+}
+
+pub fn translate(body: &Body, delta_x: i32, delta_y: i32, map_size: Point) -> Option<Body> {
+    let shift = |index: usize| {
+        let pos = get_point(index, map_size);
+        let x = pos.x as i32 + delta_x;
+        let y = pos.y as i32 + delta_y;
+
+        if x < 0 || x >= map_size.x as i32 || y < 0 || y >= map_size.y as i32 {
+            None
+        } else {
+            Some(get_index(x as u32, y as u32, map_size))
+        }
+    };
+
+    match body {
+        Body::Simple(index) => shift(*index).map(Body::Simple),
+        Body::Big(index, size) => shift(*index).map(|index| Body::Big(index, *size)),
+        Body::Snake(indices) => indices
+            .iter()
+            .map(|index| shift(*index))
+            .collect::<Option<Vec<usize>>>()
+            .map(Body::Snake),
+        Body::Shaped(index, shape) => shift(*index).map(|index| Body::Shaped(index, shape.clone())),
     }
 }
 
@@ -28,12 +187,69 @@ pub fn update_position(body: &mut Body, new_index: usize) {
     match body {
         Body::Simple(index) => *index = new_index,
         Body::Big(index, _) => *index = new_index,
+        Body::Shaped(index, _) => *index = new_index,
+        Body::Snake(indices) => {
+            indices.pop();
+            indices.insert(0, new_index);
+        }
+    }
+}
+
+pub fn grow(body: &mut Body, segments: usize) {
+    if let Body::Snake(indices) = body {
+        let tail = indices[indices.len() - 1];
+        indices.extend(std::iter::repeat_n(tail, segments));
+    }
+}
+
+pub fn shrink(body: &mut Body, segments: usize) -> Vec<usize> {
+    match body {
         Body::Snake(indices) => {
-            let mut new_indices = vec![new_index];
-            new_indices.extend_from_slice(indices);
-            new_indices.pop();
+            let length = indices.len().saturating_sub(segments).max(1);
+            let removed = indices.split_off(length);
+            get_freed_indices(indices, &removed)
+        }
+        _ => Vec::new(),
+    }
+}
+
+pub fn split(body: &mut Body, segment: usize) -> Option<Body> {
+    match body {
+        Body::Snake(indices) if segment > 0 && segment < indices.len() => {
+            Some(Body::Snake(indices.split_off(segment)))
+        }
+        _ => None,
+    }
+}
+
+pub fn get_segment(body: &Body, index: usize) -> Option<usize> {
+    match body {
+        Body::Snake(indices) => indices.iter().position(|i| *i == index),
+        _ => None,
+    }
+}
+
+pub fn get_freed_indices(remaining: &[usize], removed: &[usize]) -> Vec<usize> {
+    let mut freed = Vec::new();
+
+    for index in removed {
+        if !remaining.contains(index) && !freed.contains(index) {
+            freed.push(*index);
+        }
+    }
+
+    freed
+}
+
+pub fn turn(body: &mut Body, dir: Direction) {
+    let is_cardinal = matches!(
+        dir,
+        Direction::North | Direction::East | Direction::South | Direction::West
+    );
 
-            *indices = new_indices;
+    if let Body::Shaped(_, shape) = body {
+        if shape.rotates && is_cardinal {
+            shape.facing = dir;
         }
     }
 }
@@ -43,6 +259,8 @@ mod tests {
     use super::Body::*;
     use super::*;
 
+    const SIZE: Point = Point { x: 4, y: 3 };
+
     #[test]
     fn test_get_component_type() {
         assert_eq!(Body::get_component_type(), "Body");
@@ -63,6 +281,57 @@ mod tests {
         assert_eq!(get_position(&Snake(vec![1, 2, 3, 4])), 1);
     }
 
+    #[test]
+    fn test_get_indices_simple() {
+        assert_eq!(get_indices(&Simple(3), SIZE), vec![3]);
+    }
+
+    #[test]
+    fn test_get_indices_big() {
+        assert_eq!(get_indices(&Big(1, 2), SIZE), vec![1, 2, 5, 6]);
+    }
+
+    #[test]
+    fn test_get_indices_snake() {
+        assert_eq!(get_indices(&Snake(vec![1, 2, 6, 6]), SIZE), vec![1, 2, 6]);
+    }
+
+    #[test]
+    fn test_set_position_simple() {
+        let mut body = Simple(3);
+        set_position(&mut body, 7);
+
+        assert_eq!(body, Simple(7));
+    }
+
+    #[test]
+    fn test_set_position_big() {
+        let mut body = Big(3, 4);
+        set_position(&mut body, 7);
+
+        assert_eq!(body, Big(7, 4));
+    }
+
+    #[test]
+    fn test_set_position_snake() {
+        let mut body = Snake(vec![3, 4, 5]);
+        set_position(&mut body, 7);
+
+        assert_eq!(body, Snake(vec![7, 7, 7]));
+    }
+
+    #[test]
+    fn test_translate() {
+        assert_eq!(translate(&Simple(0), 1, 2, SIZE), Some(Simple(9)));
+        assert_eq!(translate(&Big(5, 2), -1, -1, SIZE), Some(Big(0, 2)));
+        assert_eq!(
+            translate(&Snake(vec![1, 2, 6]), 1, 0, SIZE),
+            Some(Snake(vec![2, 3, 7]))
+        );
+        assert_eq!(translate(&Snake(vec![2, 3, 7]), 1, 0, SIZE), None);
+        assert_eq!(translate(&Simple(0), -1, 0, SIZE), None);
+    }
+
     #[test]
     fn test_update_position_simple() {
         let mut body = Simple(3);
@@ -86,4 +355,142 @@ mod tests {
 
         assert_eq!(body, Snake(vec![2, 3, 4]));
     }
+
+    #[test]
+    fn test_shape_rectangle() {
+        assert_eq!(
+            Shape::rectangle(Point { x: 3, y: 2 }).offsets,
+            vec![(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_shape_get_offsets() {
+        let mut shape = Shape::new(vec![(0, 0), (0, 1), (1, 0)]);
+
+        assert_eq!(shape.get_offsets(), vec![(0, 0), (0, 1), (1, 0)]);
+
+        shape.facing = Direction::East;
+        assert_eq!(shape.get_offsets(), vec![(0, 0), (1, 0), (0, -1)]);
+
+        shape.facing = Direction::South;
+        assert_eq!(shape.get_offsets(), vec![(0, 0), (0, -1), (-1, 0)]);
+
+        shape.facing = Direction::West;
+        assert_eq!(shape.get_offsets(), vec![(0, 0), (-1, 0), (0, 1)]);
+    }
+
+    #[test]
+    fn test_get_indices_shaped() {
+        let body = Shaped(1, Shape::rectangle(Point { x: 3, y: 2 }));
+
+        assert_eq!(get_indices(&body, SIZE), vec![1, 2, 3, 5, 6, 7]);
+        assert_eq!(get_position(&body), 1);
+    }
+
+    #[test]
+    fn test_get_checked_indices() {
+        let wagon = Shape::rectangle(Point { x: 3, y: 2 });
+
+        assert_eq!(
+            get_checked_indices(&Shaped(1, wagon.clone()), SIZE),
+            Some(vec![1, 2, 3, 5, 6, 7])
+        );
+        assert_eq!(get_checked_indices(&Shaped(2, wagon), SIZE), None);
+        assert_eq!(
+            get_checked_indices(&Big(5, 2), SIZE),
+            Some(vec![5, 6, 9, 10])
+        );
+        assert_eq!(get_checked_indices(&Big(7, 2), SIZE), None);
+        assert_eq!(get_checked_indices(&Simple(7), SIZE), Some(vec![7]));
+    }
+
+    #[test]
+    fn test_turn() {
+        let shape = Shape::new(vec![(0, 0), (0, 1)]);
+        let mut fixed = Shaped(0, shape.clone());
+        let mut rotating = Shaped(0, shape.rotating());
+        let mut simple = Simple(0);
+
+        turn(&mut fixed, Direction::East);
+        turn(&mut rotating, Direction::East);
+        turn(&mut simple, Direction::East);
+
+        assert_eq!(fixed, Shaped(0, Shape::new(vec![(0, 0), (0, 1)])));
+        assert_eq!(
+            rotating,
+            Shaped(
+                0,
+                Shape {
+                    offsets: vec![(0, 0), (0, 1)],
+                    facing: Direction::East,
+                    rotates: true,
+                }
+            )
+        );
+        assert_eq!(simple, Simple(0));
+
+        turn(&mut rotating, Direction::NorthWest);
+
+        assert_eq!(
+            get_indices(&rotating, SIZE),
+            get_indices(&Shaped(0, Shape::new(vec![(0, 0), (1, 0)])), SIZE)
+        );
+    }
+
+    #[test]
+    fn test_grow() {
+        let mut body = Snake(vec![3, 4]);
+
+        grow(&mut body, 2);
+
+        assert_eq!(body, Snake(vec![3, 4, 4, 4]));
+    }
+
+    #[test]
+    fn test_grow_other() {
+        let mut body = Simple(3);
+
+        grow(&mut body, 2);
+
+        assert_eq!(body, Simple(3));
+    }
+
+    #[test]
+    fn test_shrink() {
+        let mut body = Snake(vec![3, 4, 5, 5, 1]);
+
+        assert_eq!(shrink(&mut body, 3), vec![5, 1]);
+        assert_eq!(body, Snake(vec![3, 4]));
+        assert_eq!(shrink(&mut body, 5), vec![4]);
+        assert_eq!(body, Snake(vec![3]));
+    }
+
+    #[test]
+    fn test_shrink_stacked() {
+        let mut body = Snake(vec![3; 5]);
+
+        assert_eq!(shrink(&mut body, 2), Vec::new());
+        assert_eq!(body, Snake(vec![3; 3]));
+    }
+
+    #[test]
+    fn test_split() {
+        let mut body = Snake(vec![3, 4, 5, 6]);
+
+        assert_eq!(split(&mut body, 2), Some(Snake(vec![5, 6])));
+        assert_eq!(body, Snake(vec![3, 4]));
+        assert_eq!(split(&mut body, 0), None);
+        assert_eq!(split(&mut body, 2), None);
+        assert_eq!(split(&mut Simple(3), 1), None);
+    }
+
+    #[test]
+    fn test_get_segment() {
+        let body = Snake(vec![3, 4, 5, 5]);
+
+        assert_eq!(get_segment(&body, 5), Some(2));
+        assert_eq!(get_segment(&body, 6), None);
+        assert_eq!(get_segment(&Simple(5), 5), None);
+    }
 }
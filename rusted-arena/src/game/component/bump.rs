@@ -0,0 +1,61 @@
+use crate::utils::ecs::component::Component;
+use crate::utils::ecs::storage::ComponentMap;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BumpAction {
+    Attack,
+    Swap,
+    Push,
+    Block,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bump {
+    pub ally: BumpAction,
+    pub enemy: BumpAction,
+}
+
+impl Bump {
+    pub fn get_action(&self, is_ally: bool) -> BumpAction {
+        if is_ally {
+            self.ally
+        } else {
+            self.enemy
+        }
+    }
+}
+
+impl Component for Bump {
+    type Storage = ComponentMap<Self>;
+
+    fn get_component_type() -> &'static str {
+        "Bump"
+    }
+}
+
+impl Default for Bump {
+    fn default() -> Bump {
+        Bump {
+            ally: BumpAction::Swap,
+            enemy: BumpAction::Attack,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_component_type() {
+        assert_eq!(Bump::get_component_type(), "Bump");
+    }
+
+    #[test]
+    fn test_default() {
+        let bump = Bump::default();
+
+        assert_eq!(bump.get_action(true), BumpAction::Swap);
+        assert_eq!(bump.get_action(false), BumpAction::Attack);
+    }
+}
@@ -0,0 +1,43 @@
+use crate::utils::ecs::component::Component;
+use crate::utils::ecs::storage::ComponentMap;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Faction {
+    pub id: usize,
+}
+
+impl Component for Faction {
+    type Storage = ComponentMap<Self>;
+
+    fn get_component_type() -> &'static str {
+        "Faction"
+    }
+}
+
+pub fn is_ally(faction: Option<&Faction>, other: Option<&Faction>) -> bool {
+    match (faction, other) {
+        (Some(faction), Some(other)) => faction.id == other.id,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_component_type() {
+        assert_eq!(Faction::get_component_type(), "Faction");
+    }
+
+    #[test]
+    fn test_is_ally() {
+        let a = Faction { id: 0 };
+        let b = Faction { id: 1 };
+
+        assert!(is_ally(Some(&a), Some(&a)));
+        assert!(!is_ally(Some(&a), Some(&b)));
+        assert!(!is_ally(Some(&a), None));
+        assert!(!is_ally(None, None));
+    }
+}
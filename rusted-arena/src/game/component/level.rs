@@ -0,0 +1,25 @@
+use crate::utils::ecs::component::Component;
+use crate::utils::ecs::storage::ComponentMap;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Level {
+    pub id: usize,
+}
+
+impl Component for Level {
+    type Storage = ComponentMap<Self>;
+
+    fn get_component_type() -> &'static str {
+        "Level"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_component_type() {
+        assert_eq!(Level::get_component_type(), "Level");
+    }
+}
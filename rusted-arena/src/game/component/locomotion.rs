@@ -0,0 +1,26 @@
+use crate::game::map::mobility::Mobility;
+use crate::utils::ecs::component::Component;
+use crate::utils::ecs::storage::ComponentMap;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Locomotion {
+    pub mobility: Mobility,
+}
+
+impl Component for Locomotion {
+    type Storage = ComponentMap<Self>;
+
+    fn get_component_type() -> &'static str {
+        "Locomotion"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_component_type() {
+        assert_eq!(Locomotion::get_component_type(), "Locomotion");
+    }
+}
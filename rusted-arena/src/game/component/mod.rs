@@ -1,4 +1,11 @@
 pub mod body;
+pub mod bump;
+pub mod faction;
 pub mod graphic;
 pub mod health;
+pub mod level;
+pub mod locomotion;
+pub mod occupancy;
+pub mod speed;
 pub mod stats;
+pub mod trigger;
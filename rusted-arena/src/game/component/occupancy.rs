@@ -0,0 +1,26 @@
+use crate::game::map::layer::Layer;
+use crate::utils::ecs::component::Component;
+use crate::utils::ecs::storage::ComponentMap;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Occupancy {
+    pub layer: Layer,
+}
+
+impl Component for Occupancy {
+    type Storage = ComponentMap<Self>;
+
+    fn get_component_type() -> &'static str {
+        "Occupancy"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_component_type() {
+        assert_eq!(Occupancy::get_component_type(), "Occupancy");
+    }
+}
@@ -0,0 +1,52 @@
+use crate::utils::ecs::component::Component;
+use crate::utils::ecs::storage::ComponentMap;
+
+pub const NORMAL_SPEED: u32 = 100;
+pub const SPEED_PER_MOVEMENT_POINT: u32 = 20;
+
+pub fn get_movement_points(speed: u32) -> u32 {
+    speed / SPEED_PER_MOVEMENT_POINT
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Speed {
+    pub value: u32,
+}
+
+impl Component for Speed {
+    type Storage = ComponentMap<Self>;
+
+    fn get_component_type() -> &'static str {
+        "Speed"
+    }
+}
+
+impl Default for Speed {
+    fn default() -> Speed {
+        Speed {
+            value: NORMAL_SPEED,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_component_type() {
+        assert_eq!(Speed::get_component_type(), "Speed");
+    }
+
+    #[test]
+    fn test_get_movement_points() {
+        assert_eq!(get_movement_points(NORMAL_SPEED), 5);
+        assert_eq!(get_movement_points(150), 7);
+        assert_eq!(get_movement_points(10), 0);
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(Speed::default().value, NORMAL_SPEED);
+    }
+}
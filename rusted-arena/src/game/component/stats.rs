@@ -1,10 +1,12 @@
-use crate::game::rpg::character::skill::Skill;
+use crate::game::rpg::character::attribute::Attribute;
+use crate::game::rpg::character::skill::{Skill, SkillDefault};
 use crate::utils::ecs::component::Component;
 use crate::utils::ecs::storage::ComponentMap;
 use std::collections::HashMap;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Stats {
+    attribute_map: HashMap<Attribute, i32>,
     skill_map: HashMap<usize, i32>,
 }
 
@@ -17,31 +19,46 @@ impl Component for Stats {
 }
 
 impl Stats {
-    pub fn new(skill_map: HashMap<usize, i32>) -> Stats {
-        Stats { skill_map }
+    pub fn new(attribute_map: HashMap<Attribute, i32>, skill_map: HashMap<usize, i32>) -> Stats {
+        Stats {
+            attribute_map,
+            skill_map,
+        }
     }
 
-    pub fn get_skill_rank(&self, skill: &Skill) -> Option<i32> {
-        let rank;
+    pub fn get_attribute_rank(&self, attribute: Attribute) -> Option<i32> {
+        self.attribute_map.get(&attribute).copied()
+    }
 
+    pub fn get_skill_rank(&self, skill: &Skill) -> Option<i32> {
         if let Some(base) = self.skill_map.get(&skill.id) {
-            rank = *base;
-        } else if let Some(default) = skill.default {
-            rank = default;
-        } else {
-            return None;
+            return Some(*base);
         }
 
-        Some(rank)
+        match skill.default? {
+            SkillDefault::Fixed(rank) => Some(rank),
+            SkillDefault::Attribute {
+                attribute,
+                modifier,
+            } => self
+                .get_attribute_rank(attribute)
+                .map(|rank| rank + modifier),
+        }
     }
 }
 
 #[derive(Default)]
 pub struct StatsBuilder {
+    attribute_map: HashMap<Attribute, i32>,
     skill_map: HashMap<usize, i32>,
 }
 
 impl StatsBuilder {
+    pub fn add_attribute(mut self, attribute: Attribute, rank: i32) -> StatsBuilder {
+        self.attribute_map.insert(attribute, rank);
+        self
+    }
+
     pub fn add_skill(mut self, skill: &Skill, rank: i32) -> StatsBuilder {
         self.skill_map.insert(skill.id, rank);
         self
@@ -49,6 +66,7 @@ impl StatsBuilder {
 
     pub fn build(self) -> Stats {
         Stats {
+            attribute_map: self.attribute_map,
             skill_map: self.skill_map,
         }
     }
@@ -57,6 +75,7 @@ impl StatsBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::fixture::create_toughness;
 
     #[test]
     fn test_get_component_type() {
@@ -86,6 +105,41 @@ mod tests {
         assert_eq!(stats.get_skill_rank(&skill_b), Some(-3));
     }
 
+    #[test]
+    fn test_get_attribute_rank() {
+        let stats = StatsBuilder::default()
+            .add_attribute(Attribute::Strength, 5)
+            .build();
+
+        assert_eq!(stats.get_attribute_rank(Attribute::Strength), Some(5));
+        assert_eq!(stats.get_attribute_rank(Attribute::Agility), None);
+    }
+
+    #[test]
+    fn test_get_skill_rank_default_from_attribute() {
+        let toughness = create_toughness();
+        let stats = StatsBuilder::default()
+            .add_attribute(Attribute::Health, 6)
+            .build();
+
+        assert_eq!(stats.get_skill_rank(&toughness), Some(4));
+        assert_eq!(
+            StatsBuilder::default().build().get_skill_rank(&toughness),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_skill_rank_ignores_default() {
+        let toughness = create_toughness();
+        let stats = StatsBuilder::default()
+            .add_attribute(Attribute::Health, 6)
+            .add_skill(&toughness, 7)
+            .build();
+
+        assert_eq!(stats.get_skill_rank(&toughness), Some(7));
+    }
+
     fn create_a() -> Skill {
         Skill {
             id: 0,
@@ -98,7 +152,7 @@ mod tests {
         Skill {
             id: 1,
             name: "B".to_string(),
-            default: Some(-3),
+            default: Some(SkillDefault::Fixed(-3)),
         }
     }
 }
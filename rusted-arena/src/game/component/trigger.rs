@@ -0,0 +1,34 @@
+use crate::game::rpg::combat::damage::Damage;
+use crate::utils::ecs::component::Component;
+use crate::utils::ecs::storage::ComponentMap;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TriggerEvent {
+    Enter,
+    Stay,
+    Leave,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trigger {
+    pub event: TriggerEvent,
+    pub damage: Damage,
+}
+
+impl Component for Trigger {
+    type Storage = ComponentMap<Self>;
+
+    fn get_component_type() -> &'static str {
+        "Trigger"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_component_type() {
+        assert_eq!(Trigger::get_component_type(), "Trigger");
+    }
+}
@@ -0,0 +1,48 @@
+use crate::game::component::body::Body;
+use crate::game::component::health::Health;
+use crate::game::component::stats::{Stats, StatsBuilder};
+use crate::game::rpg::character::attribute::Attribute;
+use crate::game::rpg::character::skill::{Skill, SkillDefault};
+use crate::game::rpg::check::{CheckResult, MockChecker};
+use crate::utils::ecs::storage::ComponentStorage;
+use crate::utils::ecs::ECS;
+
+pub fn create_toughness() -> Skill {
+    Skill {
+        id: 0,
+        name: "Toughness".to_string(),
+        default: Some(SkillDefault::Attribute {
+            attribute: Attribute::Health,
+            modifier: -2,
+        }),
+    }
+}
+
+pub fn create_checker(times: usize) -> MockChecker {
+    let mut checker = MockChecker::new();
+    checker
+        .expect_check()
+        .times(times)
+        .return_const(CheckResult::Success(1));
+    checker
+}
+
+pub fn create_ecs() -> ECS {
+    let mut ecs = ECS::new();
+
+    ecs.get_storage_mgr_mut().register::<Body>();
+    ecs.get_storage_mgr_mut().register::<Health>();
+    ecs.get_storage_mgr_mut().register::<Stats>();
+
+    ecs
+}
+
+pub fn add_health(ecs: &mut ECS, entity: usize, toughness: &Skill) {
+    ecs.get_storage_mgr_mut()
+        .get_mut::<Health>()
+        .add(entity, Health::default());
+    ecs.get_storage_mgr_mut().get_mut::<Stats>().add(
+        entity,
+        StatsBuilder::default().add_skill(toughness, 4).build(),
+    );
+}
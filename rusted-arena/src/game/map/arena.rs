@@ -0,0 +1,264 @@
+use super::dijkstra::DijkstraMap;
+use super::*;
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Symmetry {
+    Horizontal,
+    Vertical,
+    Rotational,
+}
+
+impl Symmetry {
+    pub fn get_mirror(self, index: usize, size: Point) -> usize {
+        let pos = get_point(index, size);
+
+        match self {
+            Symmetry::Horizontal => get_index(size.x - 1 - pos.x, pos.y, size),
+            Symmetry::Vertical => get_index(pos.x, size.y - 1 - pos.y, size),
+            Symmetry::Rotational => size.get_area() - 1 - index,
+        }
+    }
+
+    pub fn is_source(self, index: usize, size: Point) -> bool {
+        let pos = get_point(index, size);
+
+        match self {
+            Symmetry::Horizontal => pos.x < size.x / 2,
+            Symmetry::Vertical => pos.y < size.y / 2,
+            Symmetry::Rotational => index < size.get_area() / 2,
+        }
+    }
+}
+
+pub struct Arena {
+    pub map: TileMap,
+    pub spawn_zones: Vec<Vec<usize>>,
+    pub objectives: Vec<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fairness {
+    pub distances: Vec<Vec<Option<i32>>>,
+}
+
+impl Fairness {
+    pub fn get_max_difference(&self) -> Option<i32> {
+        let mut profiles = Vec::new();
+
+        for team_distances in &self.distances {
+            let mut profile = team_distances
+                .iter()
+                .copied()
+                .collect::<Option<Vec<i32>>>()?;
+            profile.sort_unstable();
+            profiles.push(profile);
+        }
+
+        let mut max_difference = 0;
+
+        if let Some((first, others)) = profiles.split_first() {
+            for profile in others {
+                for (a, b) in first.iter().zip(profile) {
+                    max_difference = max_difference.max((a - b).abs());
+                }
+            }
+        }
+
+        Some(max_difference)
+    }
+
+    pub fn is_fair(&self, tolerance: i32) -> bool {
+        self.get_max_difference()
+            .is_some_and(|difference| difference <= tolerance)
+    }
+}
+
+pub fn measure_fairness(arena: &Arena) -> Fairness {
+    let map = &arena.map;
+    let distances = arena
+        .spawn_zones
+        .iter()
+        .map(|zone| {
+            let goals: Vec<(usize, i32)> = zone.iter().map(|index| (*index, 0)).collect();
+            let dijkstra_map = DijkstraMap::new(map, &goals, |index| {
+                let tile = map.get_tile(index);
+
+                if tile.is_walkable() {
+                    Some(tile.get_movement_cost())
+                } else {
+                    None
+                }
+            });
+
+            arena
+                .objectives
+                .iter()
+                .map(|objective| dijkstra_map.get_value(*objective))
+                .collect()
+        })
+        .collect();
+
+    Fairness { distances }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Symmetry::*;
+    use super::*;
+    use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::TileType::*;
+
+    const SIZE: Point = Point { x: 5, y: 3 };
+
+    #[test]
+    fn test_get_mirror() {
+        assert_eq!(
+            Horizontal.get_mirror(get_index(1, 2, SIZE), SIZE),
+            get_index(3, 2, SIZE)
+        );
+        assert_eq!(
+            Vertical.get_mirror(get_index(1, 2, SIZE), SIZE),
+            get_index(1, 0, SIZE)
+        );
+        assert_eq!(
+            Rotational.get_mirror(get_index(1, 2, SIZE), SIZE),
+            get_index(3, 0, SIZE)
+        );
+        assert_eq!(
+            Rotational.get_mirror(get_index(2, 1, SIZE), SIZE),
+            get_index(2, 1, SIZE)
+        );
+    }
+
+    #[test]
+    fn test_is_source() {
+        assert!(Horizontal.is_source(get_index(1, 2, SIZE), SIZE));
+        assert!(!Horizontal.is_source(get_index(2, 2, SIZE), SIZE));
+        assert!(Vertical.is_source(get_index(4, 0, SIZE), SIZE));
+        assert!(!Vertical.is_source(get_index(4, 1, SIZE), SIZE));
+        assert!(Rotational.is_source(get_index(1, 1, SIZE), SIZE));
+        assert!(!Rotational.is_source(get_index(2, 1, SIZE), SIZE));
+    }
+
+    #[test]
+    fn test_mirror_tiles() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(0, 0), Wall)
+            .set_tile(xy(4, 2), Water)
+            .set_tile(xy(1, 1), Lava)
+            .mirror(Rotational)
+            .build();
+
+        assert_eq!(map.get_tile(get_index(4, 2, SIZE)), Wall);
+        assert_eq!(map.get_tile(get_index(3, 1, SIZE)), Lava);
+        assert_eq!(map.get_tile(get_index(0, 0, SIZE)), Wall);
+    }
+
+    #[test]
+    fn test_mirror_hit_points_and_levers() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(0, 1), Lever)
+            .set_tile(xy(1, 0), DoorClosed)
+            .set_tile(xy(1, 2), Wall)
+            .set_hit_points(xy(1, 2), 5)
+            .connect_lever(xy(0, 1), xy(1, 0))
+            .mirror(Horizontal)
+            .build();
+
+        assert_eq!(map.get_hit_points(get_index(3, 2, SIZE)), Some(5));
+        assert_eq!(
+            map.get_lever_targets(get_index(4, 1, SIZE)),
+            &[get_index(3, 0, SIZE)]
+        );
+    }
+
+    #[test]
+    fn test_build_arena() {
+        let arena = TileMapBuilder::new(SIZE, Floor)
+            .add_spawn_zone(xy(0, 0), xy(1, 2))
+            .add_objective(xy(2, 1))
+            .add_objective(xy(2, 0))
+            .build_arena(Vertical);
+
+        assert_eq!(arena.spawn_zones, vec![vec![0], vec![10]]);
+        assert_eq!(arena.objectives, vec![7, 2, 12]);
+        assert_zones_are_disjoint(&arena);
+    }
+
+    #[test]
+    fn test_build_arena_clips_zone_at_axis() {
+        for symmetry in [Horizontal, Vertical, Rotational].iter() {
+            let arena = TileMapBuilder::new(SIZE, Floor)
+                .add_spawn_zone(xy(0, 0), SIZE)
+                .build_arena(*symmetry);
+
+            assert!(arena.spawn_zones[0]
+                .iter()
+                .all(|index| symmetry.is_source(*index, SIZE)));
+            assert_zones_are_disjoint(&arena);
+        }
+    }
+
+    #[test]
+    fn test_symmetric_arena_is_fair() {
+        let arena = TileMapBuilder::new(xy(7, 5), Floor)
+            .set_tile(xy(1, 1), Wall)
+            .set_tile(xy(2, 1), Wall)
+            .set_tile(xy(2, 3), Water)
+            .add_spawn_zone(xy(0, 0), xy(1, 2))
+            .add_objective(xy(3, 2))
+            .add_objective(xy(2, 4))
+            .build_arena(Rotational);
+
+        let fairness = measure_fairness(&arena);
+
+        assert_eq!(
+            fairness.distances,
+            vec![
+                vec![Some(4), Some(5), Some(4)],
+                vec![Some(4), Some(4), Some(5)]
+            ]
+        );
+        assert_eq!(fairness.get_max_difference(), Some(0));
+        assert!(fairness.is_fair(0));
+    }
+
+    #[test]
+    fn test_unfair_arena() {
+        let arena = Arena {
+            map: TileMapBuilder::new(SIZE, Floor).build(),
+            spawn_zones: vec![vec![0], vec![4]],
+            objectives: vec![1],
+        };
+
+        let fairness = measure_fairness(&arena);
+
+        assert_eq!(fairness.distances, vec![vec![Some(1)], vec![Some(3)]]);
+        assert_eq!(fairness.get_max_difference(), Some(2));
+        assert!(!fairness.is_fair(1));
+        assert!(fairness.is_fair(2));
+    }
+
+    #[test]
+    fn test_unreachable_objective() {
+        let arena = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), Wall)
+            .set_tile(xy(1, 1), Wall)
+            .set_tile(xy(1, 2), Wall)
+            .add_spawn_zone(xy(0, 0), xy(1, 1))
+            .add_objective(xy(2, 1))
+            .build_arena(Horizontal);
+
+        let fairness = measure_fairness(&arena);
+
+        assert_eq!(fairness.get_max_difference(), None);
+        assert!(!fairness.is_fair(10));
+    }
+
+    fn assert_zones_are_disjoint(arena: &Arena) {
+        let first = &arena.spawn_zones[0];
+        let second = &arena.spawn_zones[1];
+
+        assert!(first.iter().all(|index| !second.contains(index)));
+    }
+}
@@ -1,3 +1,4 @@
+use super::arena::{Arena, Symmetry};
 use super::*;
 use rusted_tiles::math::get_index;
 use rusted_tiles::math::point::*;
@@ -5,12 +6,27 @@ use rusted_tiles::math::point::*;
 pub struct TileMapBuilder {
     size: Point,
     tiles: Vec<TileType>,
+    rules: BlockingRules,
+    hit_points: HashMap<usize, u32>,
+    levers: HashMap<usize, Vec<usize>>,
+    spawn_zone: Vec<usize>,
+    objectives: Vec<usize>,
+    topology: Topology,
 }
 
 impl TileMapBuilder {
     pub fn new(size: Point, default: TileType) -> Self {
         let tiles = vec![default; size.get_area()];
-        TileMapBuilder { size, tiles }
+        TileMapBuilder {
+            size,
+            tiles,
+            rules: BlockingRules::default(),
+            hit_points: HashMap::new(),
+            levers: HashMap::new(),
+            spawn_zone: Vec::new(),
+            objectives: Vec::new(),
+            topology: Topology::Square4,
+        }
     }
 
     pub fn add_border(self, tile_type: TileType) -> Self {
@@ -39,11 +55,123 @@ impl TileMapBuilder {
         self
     }
 
+    pub fn set_hit_points(mut self, pos: Point, hit_points: u32) -> Self {
+        self.hit_points
+            .insert(get_index(pos.x, pos.y, self.size), hit_points);
+        self
+    }
+
+    pub fn connect_lever(mut self, lever: Point, target: Point) -> Self {
+        self.levers
+            .entry(get_index(lever.x, lever.y, self.size))
+            .or_default()
+            .push(get_index(target.x, target.y, self.size));
+        self
+    }
+
+    pub fn add_spawn_zone(mut self, pos: Point, size: Point) -> Self {
+        for y in pos.y..(pos.y + size.y) {
+            for x in pos.x..(pos.x + size.x) {
+                self.spawn_zone.push(get_index(x, y, self.size));
+            }
+        }
+
+        self
+    }
+
+    pub fn add_objective(mut self, pos: Point) -> Self {
+        self.objectives.push(get_index(pos.x, pos.y, self.size));
+        self
+    }
+
+    pub fn mirror(mut self, symmetry: Symmetry) -> Self {
+        for index in 0..self.tiles.len() {
+            if !symmetry.is_source(index, self.size) {
+                continue;
+            }
+
+            let mirror = symmetry.get_mirror(index, self.size);
+            self.tiles[mirror] = self.tiles[index];
+
+            match self.hit_points.get(&index).copied() {
+                Some(hit_points) => self.hit_points.insert(mirror, hit_points),
+                None => self.hit_points.remove(&mirror),
+            };
+        }
+
+        let levers: Vec<(usize, Vec<usize>)> = self
+            .levers
+            .iter()
+            .filter(|(lever, _)| symmetry.is_source(**lever, self.size))
+            .map(|(lever, targets)| {
+                let targets = targets
+                    .iter()
+                    .map(|target| symmetry.get_mirror(*target, self.size))
+                    .collect();
+                (symmetry.get_mirror(*lever, self.size), targets)
+            })
+            .collect();
+
+        let size = self.size;
+        self.levers.retain(|lever, _| {
+            symmetry.is_source(*lever, size) || symmetry.get_mirror(*lever, size) == *lever
+        });
+        self.levers.extend(levers);
+        self
+    }
+
+    pub fn build_arena(self, symmetry: Symmetry) -> Arena {
+        let size = self.size;
+        let spawn_zone: Vec<usize> = self
+            .spawn_zone
+            .iter()
+            .copied()
+            .filter(|index| symmetry.is_source(*index, size))
+            .collect();
+        let mirrored_zone = spawn_zone
+            .iter()
+            .map(|index| symmetry.get_mirror(*index, size))
+            .collect();
+        let mut objectives = self.objectives.clone();
+
+        for objective in &self.objectives {
+            let mirror = symmetry.get_mirror(*objective, size);
+
+            if !objectives.contains(&mirror) {
+                objectives.push(mirror);
+            }
+        }
+
+        Arena {
+            map: self.mirror(symmetry).build(),
+            spawn_zones: vec![spawn_zone, mirrored_zone],
+            objectives,
+        }
+    }
+
+    pub fn set_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    pub fn set_blocking_rules(mut self, rules: BlockingRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
     pub fn build(self) -> TileMap {
         TileMap {
             size: self.size,
             tiles: self.tiles,
-            entities: HashMap::new(),
+            entities: LAYERS
+                .iter()
+                .map(|layer| (*layer, HashMap::new()))
+                .collect(),
+            rules: self.rules,
+            hit_points: self.hit_points,
+            levers: self.levers,
+            changes: Vec::new(),
+            topology: self.topology,
         }
     }
 }
@@ -0,0 +1,238 @@
+use super::*;
+use std::collections::VecDeque;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regions {
+    labels: Vec<Option<usize>>,
+    count: usize,
+}
+
+impl Regions {
+    pub fn get_region(&self, index: usize) -> Option<usize> {
+        self.labels.get(index).copied().flatten()
+    }
+
+    pub fn get_count(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_connected(&self, a: usize, b: usize) -> bool {
+        match (self.get_region(a), self.get_region(b)) {
+            (Some(region_a), Some(region_b)) => region_a == region_b,
+            _ => false,
+        }
+    }
+}
+
+pub fn label_regions<F>(map: &TileMap, is_passable: F) -> Regions
+where
+    F: Fn(usize) -> bool,
+{
+    let mut labels = vec![None; map.tiles.len()];
+    let mut count = 0;
+
+    for start in 0..map.tiles.len() {
+        if labels[start].is_some() || !is_passable(start) {
+            continue;
+        }
+
+        labels[start] = Some(count);
+        let mut open = vec![start];
+
+        while let Some(index) = open.pop() {
+            for neighbor in get_passable_neighbors(map, index, &is_passable) {
+                if labels[neighbor].is_none() {
+                    labels[neighbor] = Some(count);
+                    open.push(neighbor);
+                }
+            }
+        }
+
+        count += 1;
+    }
+
+    Regions { labels, count }
+}
+
+pub fn is_reachable<F>(map: &TileMap, from: usize, to: usize, can_enter: F) -> bool
+where
+    F: Fn(usize) -> bool,
+{
+    map.assert_inside(from);
+    map.assert_inside(to);
+
+    let mut visited = vec![false; map.tiles.len()];
+    let mut open = VecDeque::new();
+
+    visited[from] = true;
+    open.push_back(from);
+
+    while let Some(index) = open.pop_front() {
+        if index == to {
+            return true;
+        }
+
+        for neighbor in get_passable_neighbors(map, index, &can_enter) {
+            if !visited[neighbor] {
+                visited[neighbor] = true;
+                open.push_back(neighbor);
+            }
+        }
+    }
+
+    false
+}
+
+pub fn find_chokepoints<F>(map: &TileMap, is_passable: F) -> Vec<usize>
+where
+    F: Fn(usize) -> bool,
+{
+    let n = map.tiles.len();
+    let mut discovery = vec![0; n];
+    let mut low = vec![0; n];
+    let mut parent: Vec<Option<usize>> = vec![None; n];
+    let mut is_chokepoint = vec![false; n];
+    let mut time = 0;
+
+    for root in 0..n {
+        if discovery[root] != 0 || !is_passable(root) {
+            continue;
+        }
+
+        time += 1;
+        discovery[root] = time;
+        low[root] = time;
+
+        let mut root_children = 0;
+        let mut stack = vec![(root, 0)];
+
+        while let Some(&(index, next)) = stack.last() {
+            let neighbors = get_passable_neighbors(map, index, &is_passable);
+
+            if next < neighbors.len() {
+                if let Some(top) = stack.last_mut() {
+                    top.1 += 1;
+                }
+
+                let neighbor = neighbors[next];
+
+                if discovery[neighbor] == 0 {
+                    time += 1;
+                    discovery[neighbor] = time;
+                    low[neighbor] = time;
+                    parent[neighbor] = Some(index);
+
+                    if index == root {
+                        root_children += 1;
+                    }
+
+                    stack.push((neighbor, 0));
+                } else if parent[index] != Some(neighbor) {
+                    low[index] = low[index].min(discovery[neighbor]);
+                }
+            } else {
+                stack.pop();
+
+                if let Some(p) = parent[index] {
+                    low[p] = low[p].min(low[index]);
+
+                    if p != root && low[index] >= discovery[p] {
+                        is_chokepoint[p] = true;
+                    }
+                }
+            }
+        }
+
+        if root_children > 1 {
+            is_chokepoint[root] = true;
+        }
+    }
+
+    (0..n).filter(|i| is_chokepoint[*i]).collect()
+}
+
+fn get_passable_neighbors<F>(map: &TileMap, index: usize, is_passable: &F) -> Vec<usize>
+where
+    F: Fn(usize) -> bool,
+{
+    map.get_neighbors(index)
+        .into_iter()
+        .filter(|neighbor| is_passable(*neighbor))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::TileType::*;
+
+    const SIZE: Point = Point { x: 5, y: 3 };
+
+    fn create_map() -> TileMap {
+        TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(2, 0), Wall)
+            .set_tile(xy(2, 2), Wall)
+            .build()
+    }
+
+    fn is_walkable(map: &TileMap, index: usize) -> bool {
+        map.get_tile(index).is_walkable()
+    }
+
+    #[test]
+    fn test_label_regions() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(2, 0), Wall)
+            .set_tile(xy(2, 1), Wall)
+            .set_tile(xy(2, 2), Wall)
+            .build();
+
+        let regions = label_regions(&map, |i| is_walkable(&map, i));
+
+        assert_eq!(regions.get_count(), 2);
+        assert_eq!(regions.get_region(0), Some(0));
+        assert_eq!(regions.get_region(2), None);
+        assert_eq!(regions.get_region(4), Some(1));
+        assert!(regions.is_connected(0, 10));
+        assert!(!regions.is_connected(0, 4));
+        assert!(!regions.is_connected(0, 2));
+    }
+
+    #[test]
+    fn test_label_regions_connected() {
+        let map = create_map();
+
+        let regions = label_regions(&map, |i| is_walkable(&map, i));
+
+        assert_eq!(regions.get_count(), 1);
+        assert!(regions.is_connected(0, 14));
+    }
+
+    #[test]
+    fn test_is_reachable() {
+        let map = create_map();
+
+        assert!(is_reachable(&map, 0, 14, |i| is_walkable(&map, i)));
+        assert!(!is_reachable(&map, 0, 14, |i| is_walkable(&map, i) && i != 7));
+        assert!(is_reachable(&map, 3, 3, |_| false));
+    }
+
+    #[test]
+    fn test_find_chokepoints() {
+        let map = create_map();
+
+        let chokepoints = find_chokepoints(&map, |i| is_walkable(&map, i));
+
+        assert_eq!(chokepoints, vec![6, 7, 8]);
+    }
+
+    #[test]
+    fn test_find_no_chokepoints_in_open_area() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+
+        let chokepoints = find_chokepoints(&map, |i| is_walkable(&map, i));
+
+        assert!(chokepoints.is_empty());
+    }
+}
@@ -0,0 +1,301 @@
+use super::*;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub const UNREACHABLE: i32 = i32::MAX;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DijkstraMap {
+    goals: Vec<(usize, i32)>,
+    values: Vec<i32>,
+}
+
+impl DijkstraMap {
+    pub fn new<F>(map: &TileMap, goals: &[(usize, i32)], get_cost: F) -> DijkstraMap
+    where
+        F: Fn(usize) -> Option<u32>,
+    {
+        let mut dijkstra_map = DijkstraMap {
+            goals: goals.to_vec(),
+            values: vec![UNREACHABLE; map.tiles.len()],
+        };
+
+        for (index, weight) in goals {
+            map.assert_inside(*index);
+            dijkstra_map.values[*index] = *weight;
+        }
+
+        let seeds: Vec<usize> = goals.iter().map(|(index, _)| *index).collect();
+        dijkstra_map.relax(map, seeds, &get_cost);
+        dijkstra_map
+    }
+
+    pub fn create_flee_map<F>(&self, map: &TileMap, factor: f32, get_cost: F) -> DijkstraMap
+    where
+        F: Fn(usize) -> Option<u32>,
+    {
+        let goals: Vec<(usize, i32)> = self
+            .values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| **value != UNREACHABLE)
+            .map(|(index, value)| (index, (*value as f32 * -factor).round() as i32))
+            .collect();
+
+        DijkstraMap::new(map, &goals, get_cost)
+    }
+
+    pub fn get_value(&self, index: usize) -> Option<i32> {
+        match self.values.get(index) {
+            Some(value) if *value != UNREACHABLE => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn get_downhill(&self, map: &TileMap, index: usize) -> Option<usize> {
+        let mut best_value = self.get_value(index).unwrap_or(UNREACHABLE);
+        let mut best_neighbor = None;
+
+        for neighbor in map.get_neighbors(index) {
+            if let Some(value) = self.get_value(neighbor) {
+                if value < best_value {
+                    best_value = value;
+                    best_neighbor = Some(neighbor);
+                }
+            }
+        }
+
+        best_neighbor
+    }
+
+    pub fn update<F>(&mut self, map: &TileMap, changed: &[usize], get_cost: F)
+    where
+        F: Fn(usize) -> Option<u32>,
+    {
+        let invalid = self.invalidate(map, changed);
+        let mut seeds = Vec::new();
+
+        for &index in &invalid {
+            for neighbor in map.get_neighbors(index) {
+                if self.values[neighbor] != UNREACHABLE {
+                    seeds.push(neighbor);
+                }
+            }
+        }
+
+        for (index, weight) in &self.goals {
+            if self.values[*index] == UNREACHABLE {
+                self.values[*index] = *weight;
+                seeds.push(*index);
+            }
+        }
+
+        self.relax(map, seeds, &get_cost);
+    }
+
+    fn invalidate(&mut self, map: &TileMap, changed: &[usize]) -> Vec<usize> {
+        let mut invalid = Vec::new();
+        let mut visited = vec![false; self.values.len()];
+        let mut open: Vec<usize> = changed.to_vec();
+
+        while let Some(index) = open.pop() {
+            if visited[index] {
+                continue;
+            }
+
+            visited[index] = true;
+
+            let value = self.values[index];
+
+            if value != UNREACHABLE {
+                for neighbor in map.get_neighbors(index) {
+                    let neighbor_value = self.values[neighbor];
+
+                    if neighbor_value != UNREACHABLE && neighbor_value >= value {
+                        open.push(neighbor);
+                    }
+                }
+            }
+
+            self.values[index] = UNREACHABLE;
+            invalid.push(index);
+        }
+
+        invalid
+    }
+
+    fn relax<F>(&mut self, map: &TileMap, seeds: Vec<usize>, get_cost: &F)
+    where
+        F: Fn(usize) -> Option<u32>,
+    {
+        let mut open: BinaryHeap<Reverse<(i32, usize)>> = seeds
+            .into_iter()
+            .map(|index| Reverse((self.values[index], index)))
+            .collect();
+
+        while let Some(Reverse((value, index))) = open.pop() {
+            if value > self.values[index] {
+                continue;
+            }
+
+            for neighbor in map.get_neighbors(index) {
+                if let Some(cost) = get_cost(neighbor) {
+                    let new_value = value + cost as i32;
+
+                    if new_value < self.values[neighbor] {
+                        self.values[neighbor] = new_value;
+                        open.push(Reverse((new_value, neighbor)));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::TileType::*;
+
+    const SIZE: Point = Point { x: 5, y: 1 };
+
+    fn get_cost(map: &TileMap, index: usize) -> Option<u32> {
+        let tile = map.get_tile(index);
+
+        if tile.is_walkable() {
+            Some(tile.get_movement_cost())
+        } else {
+            None
+        }
+    }
+
+    fn assert_values(dijkstra_map: &DijkstraMap, values: &[Option<i32>]) {
+        for (index, value) in values.iter().enumerate() {
+            assert_eq!(dijkstra_map.get_value(index), *value);
+        }
+    }
+
+    #[test]
+    fn test_single_goal() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+
+        let dijkstra_map = DijkstraMap::new(&map, &[(0, 0)], |i| get_cost(&map, i));
+
+        assert_values(
+            &dijkstra_map,
+            &[Some(0), Some(1), Some(2), Some(3), Some(4)],
+        );
+    }
+
+    #[test]
+    fn test_weighted_goals() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+
+        let dijkstra_map = DijkstraMap::new(&map, &[(0, 0), (4, -2)], |i| get_cost(&map, i));
+
+        assert_values(
+            &dijkstra_map,
+            &[Some(0), Some(1), Some(0), Some(-1), Some(-2)],
+        );
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(2, 0), Wall)
+            .build();
+
+        let dijkstra_map = DijkstraMap::new(&map, &[(0, 0)], |i| get_cost(&map, i));
+
+        assert_values(&dijkstra_map, &[Some(0), Some(1), None, None, None]);
+    }
+
+    #[test]
+    fn test_flee_map() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+        let dijkstra_map = DijkstraMap::new(&map, &[(0, 0)], |i| get_cost(&map, i));
+
+        let flee_map = dijkstra_map.create_flee_map(&map, 1.2, |i| get_cost(&map, i));
+
+        assert_values(
+            &flee_map,
+            &[Some(-1), Some(-2), Some(-3), Some(-4), Some(-5)],
+        );
+        assert_eq!(flee_map.get_downhill(&map, 2), Some(3));
+    }
+
+    #[test]
+    fn test_get_downhill() {
+        let map = TileMapBuilder::new(xy(3, 3), Floor).build();
+        let dijkstra_map = DijkstraMap::new(&map, &[(4, 0)], |i| get_cost(&map, i));
+
+        assert_eq!(dijkstra_map.get_downhill(&map, 1), Some(4));
+        assert_eq!(dijkstra_map.get_downhill(&map, 3), Some(4));
+        assert_eq!(dijkstra_map.get_downhill(&map, 5), Some(4));
+        assert_eq!(dijkstra_map.get_downhill(&map, 7), Some(4));
+        assert_eq!(dijkstra_map.get_downhill(&map, 0), Some(3));
+        assert_eq!(dijkstra_map.get_downhill(&map, 4), None);
+    }
+
+    #[test]
+    fn test_update_after_blocking() {
+        let mut map = TileMapBuilder::new(xy(3, 2), Floor).build();
+        let mut dijkstra_map = DijkstraMap::new(&map, &[(0, 0)], |i| get_cost(&map, i));
+
+        map.add_entity(1, Layer::Walker, 7).unwrap();
+        dijkstra_map.update(&map, &[1], |i| {
+            if map.is_free(i, Layer::Walker, 0) == Ok(true) {
+                Some(1)
+            } else {
+                None
+            }
+        });
+
+        assert_values(
+            &dijkstra_map,
+            &[Some(0), None, Some(4), Some(1), Some(2), Some(3)],
+        );
+    }
+
+    #[test]
+    fn test_update_after_freeing() {
+        let mut map = TileMapBuilder::new(xy(3, 2), Floor).build();
+
+        map.add_entity(1, Layer::Walker, 7).unwrap();
+
+        let is_free = |map: &TileMap, i: usize| {
+            if map.is_free(i, Layer::Walker, 0) == Ok(true) {
+                Some(1)
+            } else {
+                None
+            }
+        };
+        let mut dijkstra_map = DijkstraMap::new(&map, &[(0, 0)], |i| is_free(&map, i));
+
+        map.remove_entity(1, Layer::Walker, 7).unwrap();
+        dijkstra_map.update(&map, &[1], |i| is_free(&map, i));
+
+        let expected = DijkstraMap::new(&map, &[(0, 0)], |i| is_free(&map, i));
+        assert_eq!(dijkstra_map, expected);
+    }
+
+    #[test]
+    fn test_update_across_zero_cost() {
+        let mut map = TileMapBuilder::new(xy(4, 1), Floor).build();
+
+        let get_cost = |map: &TileMap, i: usize| match i {
+            _ if map.is_free(i, Layer::Walker, 0) != Ok(true) => None,
+            2 => Some(0),
+            _ => Some(1),
+        };
+        let mut dijkstra_map = DijkstraMap::new(&map, &[(0, 0)], |i| get_cost(&map, i));
+
+        assert_values(&dijkstra_map, &[Some(0), Some(1), Some(1), Some(2)]);
+
+        map.add_entity(1, Layer::Walker, 7).unwrap();
+        dijkstra_map.update(&map, &[1], |i| get_cost(&map, i));
+
+        assert_values(&dijkstra_map, &[Some(0), None, None, None]);
+    }
+}
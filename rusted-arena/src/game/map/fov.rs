@@ -0,0 +1,351 @@
+use super::topology::{from_axial, to_axial};
+use super::*;
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Quadrant {
+    North,
+    East,
+    South,
+    West,
+}
+
+const QUADRANTS: [Quadrant; 4] = [
+    Quadrant::North,
+    Quadrant::East,
+    Quadrant::South,
+    Quadrant::West,
+];
+
+impl Quadrant {
+    fn transform(self, origin: Point, depth: i32, column: i32) -> (i32, i32) {
+        let x = origin.x as i32;
+        let y = origin.y as i32;
+
+        match self {
+            Quadrant::North => (x + column, y + depth),
+            Quadrant::East => (x + depth, y + column),
+            Quadrant::South => (x + column, y - depth),
+            Quadrant::West => (x - depth, y + column),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Slope {
+    numerator: i32,
+    denominator: i32,
+}
+
+impl Slope {
+    fn new(numerator: i32, denominator: i32) -> Slope {
+        Slope {
+            numerator,
+            denominator,
+        }
+    }
+
+    fn of_tile(depth: i32, column: i32) -> Slope {
+        Slope::new(2 * column - 1, 2 * depth)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Row {
+    depth: i32,
+    start_slope: Slope,
+    end_slope: Slope,
+}
+
+impl Row {
+    fn get_min_column(&self) -> i32 {
+        let n = 2 * self.depth * self.start_slope.numerator + self.start_slope.denominator;
+        n.div_euclid(2 * self.start_slope.denominator)
+    }
+
+    fn get_max_column(&self) -> i32 {
+        let n = 2 * self.depth * self.end_slope.numerator - self.end_slope.denominator;
+        -(-n).div_euclid(2 * self.end_slope.denominator)
+    }
+
+    fn is_symmetric(&self, column: i32) -> bool {
+        column * self.start_slope.denominator >= self.depth * self.start_slope.numerator
+            && column * self.end_slope.denominator <= self.depth * self.end_slope.numerator
+    }
+
+    fn next(&self) -> Row {
+        Row {
+            depth: self.depth + 1,
+            start_slope: self.start_slope,
+            end_slope: self.end_slope,
+        }
+    }
+}
+
+struct Scanner<'a> {
+    map: &'a TileMap,
+    origin: Point,
+    quadrant: Quadrant,
+    radius: i32,
+    visible: &'a mut HashSet<usize>,
+}
+
+impl<'a> Scanner<'a> {
+    fn get_index(&self, depth: i32, column: i32) -> Option<usize> {
+        let (x, y) = self.quadrant.transform(self.origin, depth, column);
+
+        if x < 0 || x >= self.map.size.x as i32 || y < 0 || y >= self.map.size.y as i32 {
+            return None;
+        }
+
+        Some(get_index(x as u32, y as u32, self.map.size))
+    }
+
+    fn is_in_radius(&self, depth: i32, column: i32) -> bool {
+        depth * depth + column * column <= self.radius * self.radius
+    }
+
+    fn scan(&mut self, mut row: Row) {
+        if row.depth > self.radius {
+            return;
+        }
+
+        let mut previous_is_wall = None;
+
+        for column in row.get_min_column()..=row.get_max_column() {
+            let index = self.get_index(row.depth, column);
+            let is_wall = index.is_none_or(|i| self.map.tiles[i].blocks_sight());
+
+            if let Some(i) = index {
+                if (is_wall || row.is_symmetric(column)) && self.is_in_radius(row.depth, column) {
+                    self.visible.insert(i);
+                }
+            }
+
+            if previous_is_wall == Some(true) && !is_wall {
+                row.start_slope = Slope::of_tile(row.depth, column);
+            }
+
+            if previous_is_wall == Some(false) && is_wall {
+                let mut next_row = row.next();
+                next_row.end_slope = Slope::of_tile(row.depth, column);
+                self.scan(next_row);
+            }
+
+            previous_is_wall = Some(is_wall);
+        }
+
+        if previous_is_wall == Some(false) {
+            self.scan(row.next());
+        }
+    }
+}
+
+pub fn calculate_fov(map: &TileMap, index: usize, radius: u32) -> HashSet<usize> {
+    map.assert_inside(index);
+
+    if map.get_topology() == Topology::Hex {
+        return calculate_hex_fov(map, index, radius);
+    }
+
+    let mut visible = HashSet::new();
+    let origin = get_point(index, map.size);
+
+    visible.insert(index);
+
+    for quadrant in QUADRANTS.iter() {
+        let mut scanner = Scanner {
+            map,
+            origin,
+            quadrant: *quadrant,
+            radius: radius as i32,
+            visible: &mut visible,
+        };
+
+        scanner.scan(Row {
+            depth: 1,
+            start_slope: Slope::new(-1, 1),
+            end_slope: Slope::new(1, 1),
+        });
+    }
+
+    visible
+}
+
+fn calculate_hex_fov(map: &TileMap, index: usize, radius: u32) -> HashSet<usize> {
+    let origin = get_point(index, map.size);
+    let (origin_q, origin_r) = to_axial(origin);
+    let radius = radius as i32;
+    let mut visible = HashSet::new();
+
+    for delta_q in -radius..=radius {
+        let min_r = (-radius).max(-delta_q - radius);
+        let max_r = radius.min(-delta_q + radius);
+
+        for delta_r in min_r..=max_r {
+            let (x, y) = from_axial(origin_q + delta_q, origin_r + delta_r);
+
+            if x < 0 || x >= map.size.x as i32 || y < 0 || y >= map.size.y as i32 {
+                continue;
+            }
+
+            let target = xy(x as u32, y as u32);
+
+            if is_hex_line_clear(map, origin, target) || is_hex_line_clear(map, target, origin) {
+                visible.insert(get_index(target.x, target.y, map.size));
+            }
+        }
+    }
+
+    visible
+}
+
+fn is_hex_line_clear(map: &TileMap, from: Point, to: Point) -> bool {
+    let line = Topology::Hex.get_line(from, to);
+    let length = line.len();
+
+    line[1..length.max(2) - 1].iter().all(|point| {
+        point.x < map.size.x
+            && point.y < map.size.y
+            && !map.tiles[get_index(point.x, point.y, map.size)].blocks_sight()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::TileType::*;
+
+    const SIZE: Point = Point { x: 5, y: 5 };
+
+    #[test]
+    fn test_empty_map() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+
+        let visible = calculate_fov(&map, 12, 10);
+
+        assert_eq!(visible.len(), 25);
+    }
+
+    #[test]
+    fn test_radius() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+
+        let visible = calculate_fov(&map, 12, 1);
+
+        assert_eq!(visible, to_set(&[7, 11, 12, 13, 17]));
+    }
+
+    #[test]
+    fn test_radius_zero() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+
+        assert_eq!(calculate_fov(&map, 12, 0), to_set(&[12]));
+    }
+
+    #[test]
+    fn test_wall_blocks_sight() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(2, 2), Wall)
+            .build();
+
+        let visible = calculate_fov(&map, get_index(2, 0, SIZE), 10);
+
+        assert!(visible.contains(&get_index(2, 2, SIZE)));
+        assert!(!visible.contains(&get_index(2, 3, SIZE)));
+        assert!(!visible.contains(&get_index(2, 4, SIZE)));
+        assert!(visible.contains(&get_index(0, 4, SIZE)));
+        assert!(visible.contains(&get_index(4, 4, SIZE)));
+    }
+
+    #[test]
+    fn test_room() {
+        let map = TileMapBuilder::new(SIZE, Floor).add_border(Wall).build();
+
+        let visible = calculate_fov(&map, 12, 10);
+
+        assert_eq!(visible.len(), 25);
+    }
+
+    #[test]
+    fn test_symmetry() {
+        assert_symmetric(Topology::Square4);
+    }
+
+    #[test]
+    fn test_hex_symmetry() {
+        assert_symmetric(Topology::Hex);
+    }
+
+    fn assert_symmetric(topology: Topology) {
+        let map = TileMapBuilder::new(xy(7, 6), Floor)
+            .set_topology(topology)
+            .set_tile(xy(1, 1), Wall)
+            .set_tile(xy(3, 2), Wall)
+            .set_tile(xy(4, 2), Wall)
+            .set_tile(xy(2, 4), Wall)
+            .set_tile(xy(5, 4), Wall)
+            .build();
+
+        for a in 0..42 {
+            if !map.get_tile(a).is_walkable() {
+                continue;
+            }
+
+            let visible_from_a = calculate_fov(&map, a, 10);
+
+            for b in 0..42 {
+                if !map.get_tile(b).is_walkable() {
+                    continue;
+                }
+
+                let visible_from_b = calculate_fov(&map, b, 10);
+
+                assert_eq!(visible_from_a.contains(&b), visible_from_b.contains(&a));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 25 is outside the map!")]
+    fn test_outside() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+
+        calculate_fov(&map, 25, 1);
+    }
+
+    fn to_set(indices: &[usize]) -> HashSet<usize> {
+        indices.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_hex_fov() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_topology(Topology::Hex)
+            .set_tile(xy(3, 2), Wall)
+            .build();
+
+        let near = calculate_fov(&map, 12, 1);
+        let far = calculate_fov(&map, 12, 2);
+
+        assert_eq!(near.len(), 7);
+        assert!(far.contains(&13));
+        assert!(!far.contains(&14));
+        assert!(far.contains(&2));
+    }
+
+    #[test]
+    fn test_hex_radius() {
+        let map = TileMapBuilder::new(xy(9, 9), Floor)
+            .set_topology(Topology::Hex)
+            .build();
+
+        let visible = calculate_fov(&map, 40, 3);
+
+        assert_eq!(visible.len(), 37);
+        assert!(visible
+            .iter()
+            .all(|index| map.get_distance(40, *index) <= 3));
+    }
+}
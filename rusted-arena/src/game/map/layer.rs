@@ -0,0 +1,78 @@
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum Layer {
+    Ground,
+    Walker,
+    Flyer,
+}
+
+pub const LAYERS: [Layer; 3] = [Layer::Ground, Layer::Walker, Layer::Flyer];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockingRules {
+    blocked: Vec<(Layer, Layer)>,
+}
+
+impl BlockingRules {
+    pub fn new() -> Self {
+        BlockingRules {
+            blocked: Vec::new(),
+        }
+    }
+
+    pub fn add(mut self, mover: Layer, occupant: Layer) -> Self {
+        if !self.blocks(mover, occupant) {
+            self.blocked.push((mover, occupant));
+        }
+
+        self
+    }
+
+    pub fn blocks(&self, mover: Layer, occupant: Layer) -> bool {
+        self.blocked.contains(&(mover, occupant))
+    }
+}
+
+impl Default for BlockingRules {
+    fn default() -> Self {
+        BlockingRules::new()
+            .add(Layer::Walker, Layer::Walker)
+            .add(Layer::Flyer, Layer::Flyer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Layer::*;
+    use super::*;
+
+    #[test]
+    fn test_no_rules() {
+        let rules = BlockingRules::new();
+
+        for layer in LAYERS.iter() {
+            assert!(!rules.blocks(*layer, *layer));
+        }
+    }
+
+    #[test]
+    fn test_default_rules() {
+        let rules = BlockingRules::default();
+
+        assert!(rules.blocks(Walker, Walker));
+        assert!(rules.blocks(Flyer, Flyer));
+        assert!(!rules.blocks(Ground, Ground));
+        assert!(!rules.blocks(Walker, Ground));
+        assert!(!rules.blocks(Walker, Flyer));
+        assert!(!rules.blocks(Flyer, Walker));
+        assert!(!rules.blocks(Ground, Walker));
+    }
+
+    #[test]
+    fn test_add_rule() {
+        let rules = BlockingRules::default().add(Walker, Ground);
+
+        assert!(rules.blocks(Walker, Ground));
+        assert!(rules.blocks(Walker, Walker));
+        assert!(!rules.blocks(Ground, Walker));
+    }
+}
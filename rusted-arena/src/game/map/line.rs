@@ -0,0 +1,218 @@
+use super::*;
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum LineMode {
+    Bresenham,
+    Permissive,
+}
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum LineResult {
+    Clear,
+    BlockedByTile(usize),
+    BlockedByEntity(usize),
+}
+
+pub fn get_line(from: Point, to: Point) -> Vec<Point> {
+    let mut x = from.x as i32;
+    let mut y = from.y as i32;
+    let end_x = to.x as i32;
+    let end_y = to.y as i32;
+    let delta_x = (end_x - x).abs();
+    let delta_y = -(end_y - y).abs();
+    let step_x = if x < end_x { 1 } else { -1 };
+    let step_y = if y < end_y { 1 } else { -1 };
+    let mut error = delta_x + delta_y;
+    let mut points = vec![from];
+
+    while x != end_x || y != end_y {
+        let double_error = 2 * error;
+
+        if double_error >= delta_y {
+            error += delta_y;
+            x += step_x;
+        }
+
+        if double_error <= delta_x {
+            error += delta_x;
+            y += step_y;
+        }
+
+        points.push(xy(x as u32, y as u32));
+    }
+
+    points
+}
+
+pub fn check_sight(map: &TileMap, from: usize, to: usize, mode: LineMode) -> LineResult {
+    check(map, from, to, mode, None)
+}
+
+pub fn check_fire(
+    map: &TileMap,
+    from: usize,
+    to: usize,
+    mode: LineMode,
+    ignored: &[usize],
+) -> LineResult {
+    check(map, from, to, mode, Some(ignored))
+}
+
+fn check(
+    map: &TileMap,
+    from: usize,
+    to: usize,
+    mode: LineMode,
+    ignored: Option<&[usize]>,
+) -> LineResult {
+    map.assert_inside(from);
+    map.assert_inside(to);
+
+    let start = get_point(from, map.size);
+    let end = get_point(to, map.size);
+    let topology = map.get_topology();
+    let forward = topology.get_line(start, end);
+    let result = trace(map, &forward, ignored);
+
+    if result != LineResult::Clear && mode == LineMode::Permissive {
+        let mut backward = topology.get_line(end, start);
+        backward.reverse();
+
+        if trace(map, &backward, ignored) == LineResult::Clear {
+            return LineResult::Clear;
+        }
+    }
+
+    result
+}
+
+fn trace(map: &TileMap, points: &[Point], ignored: Option<&[usize]>) -> LineResult {
+    let length = points.len();
+
+    if length < 3 {
+        return LineResult::Clear;
+    }
+
+    for point in &points[1..length - 1] {
+        let index = get_index(point.x, point.y, map.size);
+
+        if map.tiles[index].blocks_sight() {
+            return LineResult::BlockedByTile(index);
+        }
+
+        if let Some(ignored) = ignored {
+            for (layer, entity) in map.get_entities(index) {
+                if layer != Layer::Ground && !ignored.contains(&entity) {
+                    return LineResult::BlockedByEntity(entity);
+                }
+            }
+        }
+    }
+
+    LineResult::Clear
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LineMode::*;
+    use super::LineResult::*;
+    use super::*;
+    use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::TileType::*;
+
+    const SIZE: Point = Point { x: 5, y: 5 };
+
+    #[test]
+    fn test_get_line_horizontal() {
+        assert_eq!(
+            get_line(xy(1, 2), xy(4, 2)),
+            vec![xy(1, 2), xy(2, 2), xy(3, 2), xy(4, 2)]
+        );
+    }
+
+    #[test]
+    fn test_get_line_diagonal() {
+        assert_eq!(
+            get_line(xy(3, 3), xy(1, 1)),
+            vec![xy(3, 3), xy(2, 2), xy(1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_get_line_steep() {
+        assert_eq!(
+            get_line(xy(0, 0), xy(1, 3)),
+            vec![xy(0, 0), xy(0, 1), xy(1, 2), xy(1, 3)]
+        );
+    }
+
+    #[test]
+    fn test_get_line_same_point() {
+        assert_eq!(get_line(xy(2, 2), xy(2, 2)), vec![xy(2, 2)]);
+    }
+
+    #[test]
+    fn test_check_sight() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(2, 2), Wall)
+            .build();
+
+        assert_eq!(check_sight(&map, 10, 14, Bresenham), BlockedByTile(12));
+        assert_eq!(check_sight(&map, 10, 12, Bresenham), Clear);
+        assert_eq!(check_sight(&map, 5, 9, Bresenham), Clear);
+    }
+
+    #[test]
+    fn test_check_sight_ignores_entities() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        map.add_entity(12, Layer::Walker, 3).unwrap();
+
+        assert_eq!(check_sight(&map, 10, 14, Bresenham), Clear);
+    }
+
+    #[test]
+    fn test_check_permissive() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), Wall)
+            .build();
+        let from = get_index(2, 1, SIZE);
+        let to = get_index(0, 0, SIZE);
+
+        assert_eq!(check_sight(&map, from, to, Bresenham), BlockedByTile(1));
+        assert_eq!(check_sight(&map, from, to, Permissive), Clear);
+    }
+
+    #[test]
+    fn test_check_fire() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        map.add_entity(11, Layer::Walker, 3).unwrap();
+        map.add_entity(13, Layer::Walker, 4).unwrap();
+
+        assert_eq!(check_fire(&map, 10, 14, Bresenham, &[]), BlockedByEntity(3));
+        assert_eq!(
+            check_fire(&map, 10, 14, Bresenham, &[3]),
+            BlockedByEntity(4)
+        );
+        assert_eq!(check_fire(&map, 10, 14, Bresenham, &[3, 4]), Clear);
+    }
+
+    #[test]
+    fn test_check_fire_ignores_ground() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        map.add_entity(11, Layer::Ground, 3).unwrap();
+        map.add_entity(13, Layer::Flyer, 4).unwrap();
+
+        assert_eq!(check_fire(&map, 10, 14, Bresenham, &[]), BlockedByEntity(4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 25 is outside the map!")]
+    fn test_check_outside() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+
+        check_sight(&map, 0, 25, Bresenham);
+    }
+}
@@ -0,0 +1,137 @@
+use super::*;
+use std::collections::HashSet;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TileMemory {
+    tiles: Vec<Option<TileType>>,
+    visible: HashSet<usize>,
+    entities: HashMap<usize, usize>,
+}
+
+impl TileMemory {
+    pub fn new(map: &TileMap) -> TileMemory {
+        TileMemory {
+            tiles: vec![None; map.tiles.len()],
+            visible: HashSet::new(),
+            entities: HashMap::new(),
+        }
+    }
+
+    pub fn update(&mut self, map: &TileMap, visible: HashSet<usize>) {
+        let mut seen_entities: HashMap<usize, usize> = HashMap::new();
+
+        for &index in &visible {
+            map.assert_inside(index);
+            self.tiles[index] = Some(map.tiles[index]);
+
+            for (_, entity) in map.get_entities(index) {
+                let position = seen_entities.entry(entity).or_insert(index);
+
+                if index < *position {
+                    *position = index;
+                }
+            }
+        }
+
+        self.entities
+            .retain(|entity, index| seen_entities.contains_key(entity) || !visible.contains(index));
+        self.entities.extend(seen_entities);
+        self.visible = visible;
+    }
+
+    pub fn get_tile(&self, index: usize) -> Option<TileType> {
+        self.tiles.get(index).copied().flatten()
+    }
+
+    pub fn is_visible(&self, index: usize) -> bool {
+        self.visible.contains(&index)
+    }
+
+    pub fn get_last_seen(&self, entity: usize) -> Option<usize> {
+        self.entities.get(&entity).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::TileType::*;
+
+    const SIZE: Point = Point { x: 3, y: 2 };
+
+    fn to_set(indices: &[usize]) -> HashSet<usize> {
+        indices.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+        let memory = TileMemory::new(&map);
+
+        for i in 0..6 {
+            assert_eq!(memory.get_tile(i), None);
+            assert!(!memory.is_visible(i));
+        }
+    }
+
+    #[test]
+    fn test_remember_tiles() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), Wall)
+            .build();
+        let mut memory = TileMemory::new(&map);
+
+        memory.update(&map, to_set(&[0, 1]));
+        memory.update(&map, to_set(&[2]));
+
+        assert_eq!(memory.get_tile(0), Some(Floor));
+        assert_eq!(memory.get_tile(1), Some(Wall));
+        assert_eq!(memory.get_tile(2), Some(Floor));
+        assert_eq!(memory.get_tile(3), None);
+        assert!(!memory.is_visible(0));
+        assert!(!memory.is_visible(1));
+        assert!(memory.is_visible(2));
+    }
+
+    #[test]
+    fn test_remember_entities() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let mut memory = TileMemory::new(&map);
+
+        map.add_entity(4, Layer::Walker, 10).unwrap();
+        map.add_entity(5, Layer::Walker, 10).unwrap();
+        memory.update(&map, to_set(&[0, 4, 5]));
+
+        assert_eq!(memory.get_last_seen(10), Some(4));
+        assert_eq!(memory.get_last_seen(11), None);
+    }
+
+    #[test]
+    fn test_keep_last_seen_position_when_out_of_sight() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let mut memory = TileMemory::new(&map);
+
+        map.add_entity(4, Layer::Walker, 10).unwrap();
+        memory.update(&map, to_set(&[4]));
+        map.remove_entity(4, Layer::Walker, 10).unwrap();
+        map.add_entity(5, Layer::Walker, 10).unwrap();
+        memory.update(&map, to_set(&[0]));
+
+        assert_eq!(memory.get_last_seen(10), Some(4));
+    }
+
+    #[test]
+    fn test_forget_entity_that_left() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let mut memory = TileMemory::new(&map);
+
+        map.add_entity(4, Layer::Walker, 10).unwrap();
+        memory.update(&map, to_set(&[4]));
+        map.remove_entity(4, Layer::Walker, 10).unwrap();
+        map.add_entity(5, Layer::Walker, 10).unwrap();
+        memory.update(&map, to_set(&[4]));
+
+        assert_eq!(memory.get_last_seen(10), None);
+    }
+}
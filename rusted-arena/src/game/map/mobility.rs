@@ -0,0 +1,81 @@
+use super::*;
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum MovementMode {
+    Walk,
+    Fly,
+    Swim,
+    Climb,
+    Phase,
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct Mobility {
+    modes: u8,
+}
+
+impl Mobility {
+    pub fn new(modes: &[MovementMode]) -> Mobility {
+        Mobility {
+            modes: modes.iter().fold(0, |bits, mode| bits | get_bit(*mode)),
+        }
+    }
+
+    pub fn can(self, mode: MovementMode) -> bool {
+        self.modes & get_bit(mode) != 0
+    }
+
+    pub fn can_enter(self, tile: TileType) -> bool {
+        tile.get_traversal().iter().any(|mode| self.can(*mode))
+    }
+}
+
+impl Default for Mobility {
+    fn default() -> Self {
+        Mobility::new(&[MovementMode::Walk])
+    }
+}
+
+fn get_bit(mode: MovementMode) -> u8 {
+    1 << mode as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MovementMode::*;
+    use super::*;
+    use crate::game::map::TileType::*;
+
+    #[test]
+    fn test_can() {
+        let mobility = Mobility::new(&[Walk, Swim]);
+
+        assert!(mobility.can(Walk));
+        assert!(mobility.can(Swim));
+        assert!(!mobility.can(Fly));
+        assert!(!Mobility::new(&[]).can(Walk));
+        assert_eq!(Mobility::default(), Mobility::new(&[Walk]));
+    }
+
+    #[test]
+    fn test_can_enter() {
+        let walker = Mobility::default();
+        let dragon = Mobility::new(&[Walk, Fly]);
+        let ghost = Mobility::new(&[Walk, Phase]);
+        let fish = Mobility::new(&[Swim]);
+
+        assert!(walker.can_enter(Floor));
+        assert!(!walker.can_enter(Chasm));
+        assert!(!walker.can_enter(DeepWater));
+        assert!(dragon.can_enter(Chasm));
+        assert!(dragon.can_enter(Cliff));
+        assert!(!dragon.can_enter(Wall));
+        assert!(ghost.can_enter(Wall));
+        assert!(ghost.can_enter(DoorClosed));
+        assert!(!ghost.can_enter(Chasm));
+        assert!(fish.can_enter(DeepWater));
+        assert!(fish.can_enter(Water));
+        assert!(!fish.can_enter(Floor));
+        assert!(Mobility::new(&[Climb]).can_enter(Cliff));
+    }
+}
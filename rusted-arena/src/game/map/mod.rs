@@ -1,38 +1,185 @@
+pub mod arena;
 pub mod builder;
-
+pub mod connectivity;
+pub mod dijkstra;
+pub mod fov;
+pub mod layer;
+pub mod line;
+pub mod memory;
+pub mod mobility;
+pub mod pathfinding;
+pub mod query;
+pub mod terrain;
+pub mod topology;
+pub mod world;
+
+use crate::game::map::layer::*;
+use crate::game::map::memory::TileMemory;
+use crate::game::map::mobility::*;
+use crate::game::map::terrain::TileChange;
+use crate::game::map::topology::Topology;
 use rusted_tiles::math::color::*;
 use rusted_tiles::math::point::*;
 use rusted_tiles::math::{get_index, get_point};
 use rusted_tiles::rendering::tile::TileRenderer;
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
 
 #[derive(PartialEq, Copy, Clone, Debug)]
 pub enum Direction {
     North,
+    NorthEast,
     East,
+    SouthEast,
     South,
+    SouthWest,
     West,
+    NorthWest,
 }
 
+pub const MIN_MOVEMENT_COST: u32 = 1;
+
+const GROUND: [MovementMode; 2] = [MovementMode::Walk, MovementMode::Fly];
+const SOLID: [MovementMode; 1] = [MovementMode::Phase];
+const SHALLOW_WATER: [MovementMode; 3] =
+    [MovementMode::Walk, MovementMode::Swim, MovementMode::Fly];
+const DEEP_WATER: [MovementMode; 2] = [MovementMode::Swim, MovementMode::Fly];
+const CHASM: [MovementMode; 1] = [MovementMode::Fly];
+const CLIFF: [MovementMode; 2] = [MovementMode::Climb, MovementMode::Fly];
+
 #[derive(PartialEq, Copy, Clone, Debug)]
 pub enum TileType {
     Floor,
     Wall,
+    Stairs,
+    DoorOpen,
+    DoorClosed,
+    Rubble,
+    Lever,
+    Lava,
+    Water,
+    DeepWater,
+    Chasm,
+    Cliff,
 }
 
 impl TileType {
     pub fn is_walkable(self) -> bool {
+        self.get_traversal().contains(&MovementMode::Walk)
+    }
+
+    pub fn get_traversal(self) -> &'static [MovementMode] {
+        match self {
+            Self::Floor => &GROUND,
+            Self::Wall => &SOLID,
+            Self::Stairs => &GROUND,
+            Self::DoorOpen => &GROUND,
+            Self::DoorClosed => &SOLID,
+            Self::Rubble => &GROUND,
+            Self::Lever => &GROUND,
+            Self::Lava => &GROUND,
+            Self::Water => &SHALLOW_WATER,
+            Self::DeepWater => &DEEP_WATER,
+            Self::Chasm => &CHASM,
+            Self::Cliff => &CLIFF,
+        }
+    }
+
+    pub fn blocks_sight(self) -> bool {
+        match self {
+            Self::Floor => false,
+            Self::Wall => true,
+            Self::Stairs => false,
+            Self::DoorOpen => false,
+            Self::DoorClosed => true,
+            Self::Rubble => false,
+            Self::Lever => false,
+            Self::Lava => false,
+            Self::Water => false,
+            Self::DeepWater => false,
+            Self::Chasm => false,
+            Self::Cliff => true,
+        }
+    }
+
+    pub fn get_movement_cost(self) -> u32 {
+        match self {
+            Self::Floor => 1,
+            Self::Wall => 1,
+            Self::Stairs => 1,
+            Self::DoorOpen => 1,
+            Self::DoorClosed => 1,
+            Self::Rubble => 2,
+            Self::Lever => 1,
+            Self::Lava => 1,
+            Self::Water => 2,
+            Self::DeepWater => 2,
+            Self::Chasm => 1,
+            Self::Cliff => 3,
+        }
+    }
+}
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum MapError {
+    OutOfBounds(usize),
+    Blocked {
+        index: usize,
+        by: usize,
+    },
+    NotPresent {
+        index: usize,
+        entity: usize,
+    },
+    WrongEntity {
+        index: usize,
+        expected: usize,
+        found: usize,
+    },
+    WrongTile {
+        index: usize,
+        found: TileType,
+    },
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Self::Floor => true,
-            Self::Wall => false,
+            MapError::OutOfBounds(index) => write!(f, "Index {} is outside the map!", index),
+            MapError::Blocked { index, by } => {
+                write!(f, "Index {} is blocked by entity {}!", index, by)
+            }
+            MapError::NotPresent { index, entity } => {
+                write!(f, "Entity {} is not at {}!", entity, index)
+            }
+            MapError::WrongEntity {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Found entity {} instead of {} at {}!",
+                found, expected, index
+            ),
+            MapError::WrongTile { index, found } => {
+                write!(f, "Tile {:?} at {} does not support this!", found, index)
+            }
         }
     }
 }
 
+impl Error for MapError {}
+
 pub struct TileMap {
     size: Point,
     tiles: Vec<TileType>,
-    entities: HashMap<usize, usize>,
+    entities: HashMap<Layer, HashMap<usize, Vec<usize>>>,
+    rules: BlockingRules,
+    hit_points: HashMap<usize, u32>,
+    levers: HashMap<usize, Vec<usize>>,
+    changes: Vec<TileChange>,
+    topology: Topology,
 }
 
 impl TileMap {
@@ -40,43 +187,83 @@ impl TileMap {
         self.size
     }
 
+    pub fn get_topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn get_blocking_rules(&self) -> &BlockingRules {
+        &self.rules
+    }
+
+    pub fn is_inside(&self, index: usize) -> bool {
+        index < self.tiles.len()
+    }
+
+    fn check_inside(&self, index: usize) -> Result<(), MapError> {
+        if self.is_inside(index) {
+            Ok(())
+        } else {
+            Err(MapError::OutOfBounds(index))
+        }
+    }
+
     fn assert_inside(&self, index: usize) {
-        assert!(
-            index < self.tiles.len(),
-            "Index {} is outside the map!",
-            index
-        );
+        if let Err(error) = self.check_inside(index) {
+            panic!("{}", error);
+        }
     }
 
     pub fn render(&self, renderer: &mut dyn TileRenderer) {
-        let mut pos = ZERO;
-
-        for tile in self.tiles.iter() {
-            match tile {
-                TileType::Floor => {
-                    renderer.add_ascii(pos, b'.', WHITE);
-                }
-                TileType::Wall => {
-                    renderer.add_ascii(pos, b'#', WHITE);
-                }
-            }
+        for (index, tile) in self.tiles.iter().enumerate() {
+            render_tile(renderer, self.get_render_position(index), *tile, WHITE);
+        }
+    }
 
-            pos.x += 1;
+    pub fn render_memory(&self, renderer: &mut dyn TileRenderer, memory: &TileMemory) {
+        for index in 0..self.tiles.len() {
+            let pos = self.get_render_position(index);
 
-            if pos.x >= self.size.x {
-                pos.x = 0;
-                pos.y += 1;
+            if memory.is_visible(index) {
+                render_tile(renderer, pos, self.tiles[index], WHITE);
+            } else if let Some(tile) = memory.get_tile(index) {
+                render_tile(renderer, pos, tile, GRAY);
             }
         }
     }
 
+    pub fn get_tile(&self, index: usize) -> TileType {
+        self.assert_inside(index);
+        self.tiles[index]
+    }
+
+    pub fn get_directions(&self) -> &'static [Direction] {
+        self.topology.get_directions()
+    }
+
     pub fn get_neighbor(&self, index: usize, dir: Direction) -> Option<usize> {
-        match dir {
-            Direction::North => self.get_with_offset(index, 0, 1),
-            Direction::East => self.get_with_offset(index, 1, 0),
-            Direction::South => self.get_with_offset(index, 0, -1),
-            Direction::West => self.get_with_offset(index, -1, 0),
-        }
+        let pos = get_point(index, self.size);
+        let (delta_x, delta_y) = self.topology.get_offset(pos, dir)?;
+        self.get_with_offset(index, delta_x, delta_y)
+    }
+
+    pub fn get_neighbors(&self, index: usize) -> Vec<usize> {
+        let pos = get_point(index, self.size);
+
+        self.topology
+            .get_offsets(pos)
+            .iter()
+            .filter_map(|(delta_x, delta_y)| self.get_with_offset(index, *delta_x, *delta_y))
+            .collect()
+    }
+
+    pub fn get_distance(&self, from: usize, to: usize) -> u32 {
+        self.topology
+            .get_distance(get_point(from, self.size), get_point(to, self.size))
+    }
+
+    pub fn get_render_position(&self, index: usize) -> Point {
+        self.topology
+            .get_render_position(get_point(index, self.size))
     }
 
     pub fn get_with_offset(&self, index: usize, delta_x: i32, delta_y: i32) -> Option<usize> {
@@ -91,99 +278,271 @@ impl TileMap {
         Option::Some(get_index(x as u32, y as u32, self.size))
     }
 
-    pub fn is_free(&self, index: usize, entity: usize) -> bool {
-        self.assert_inside(index);
+    pub fn is_free(&self, index: usize, layer: Layer, entity: usize) -> Result<bool, MapError> {
+        self.is_free_with_mobility(index, layer, Mobility::default(), entity)
+    }
 
-        if !self.tiles[index].is_walkable() {
-            return false;
+    pub fn is_free_with_mobility(
+        &self,
+        index: usize,
+        layer: Layer,
+        mobility: Mobility,
+        entity: usize,
+    ) -> Result<bool, MapError> {
+        self.check_inside(index)?;
+
+        if !mobility.can_enter(self.tiles[index]) {
+            return Ok(false);
         }
 
-        match self.entities.get(&index) {
-            None => true,
-            Some(e) if *e == entity => true,
-            Some(_) => false,
-        }
+        Ok(self.get_blocker(index, layer, entity).is_none())
     }
 
-    pub fn is_square_free(&self, index: usize, size: u32, entity: usize) -> bool {
-        self.assert_inside(index);
-        execute_function_on_square(self.size, index, size, |i: usize| self.is_free(i, entity))
+    pub fn is_square_free(
+        &self,
+        index: usize,
+        size: u32,
+        layer: Layer,
+        entity: usize,
+    ) -> Result<bool, MapError> {
+        self.is_square_free_with_mobility(index, size, layer, Mobility::default(), entity)
+    }
+
+    pub fn is_square_free_with_mobility(
+        &self,
+        index: usize,
+        size: u32,
+        layer: Layer,
+        mobility: Mobility,
+        entity: usize,
+    ) -> Result<bool, MapError> {
+        self.check_inside(index)?;
+
+        match get_square_indices(self.size, index, size) {
+            None => Ok(false),
+            Some(indices) => {
+                for i in indices {
+                    if !self.is_free_with_mobility(i, layer, mobility, entity)? {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
+            }
+        }
     }
 
     // occupying entities
 
-    pub fn get_entity(&self, index: usize) -> Option<&usize> {
-        self.assert_inside(index);
-        self.entities.get(&index)
+    pub fn get_entity(&self, index: usize, layer: Layer) -> Option<&usize> {
+        self.get_occupants(index, layer).first()
     }
 
-    pub fn remove_entity(&mut self, index: usize, entity: usize) -> bool {
+    pub fn get_occupants(&self, index: usize, layer: Layer) -> &[usize] {
         self.assert_inside(index);
+        self.entities[&layer]
+            .get(&index)
+            .map_or(&[], |occupants| occupants.as_slice())
+    }
 
-        match self.entities.remove(&index) {
-            None => panic!("Could not remove entity {} at {}!", entity, index),
-            Some(other) if other != entity => panic!(
-                "Removed entity {} instead of {} at {}!",
-                other, entity, index
-            ),
-            _ => {}
+    pub fn get_entities(&self, index: usize) -> Vec<(Layer, usize)> {
+        LAYERS
+            .iter()
+            .flat_map(|layer| {
+                self.get_occupants(index, *layer)
+                    .iter()
+                    .map(move |entity| (*layer, *entity))
+            })
+            .collect()
+    }
+
+    pub fn get_entities_on_layer(&self, layer: Layer) -> &HashMap<usize, Vec<usize>> {
+        &self.entities[&layer]
+    }
+
+    pub fn get_blockers(&self, index: usize, layer: Layer, entity: usize) -> Vec<usize> {
+        LAYERS
+            .iter()
+            .filter(|occupant| self.rules.blocks(layer, **occupant))
+            .flat_map(|occupant| self.get_occupants(index, *occupant))
+            .filter(|e| **e != entity)
+            .copied()
+            .collect()
+    }
+
+    fn get_blocker(&self, index: usize, layer: Layer, entity: usize) -> Option<usize> {
+        self.get_blockers(index, layer, entity).first().copied()
+    }
+
+    pub fn remove_entity(
+        &mut self,
+        index: usize,
+        layer: Layer,
+        entity: usize,
+    ) -> Result<(), MapError> {
+        self.remove_entity_from_indices(&[index], layer, entity)
+    }
+
+    pub fn remove_entity_from_square(
+        &mut self,
+        index: usize,
+        size: u32,
+        layer: Layer,
+        entity: usize,
+    ) -> Result<(), MapError> {
+        let indices = self.get_square(index, size)?;
+        self.remove_entity_from_indices(&indices, layer, entity)
+    }
+
+    pub fn remove_entity_from_indices(
+        &mut self,
+        indices: &[usize],
+        layer: Layer,
+        entity: usize,
+    ) -> Result<(), MapError> {
+        for &index in indices {
+            self.check_inside(index)?;
+
+            let occupants = self.get_occupants(index, layer);
+
+            match occupants.first() {
+                None => return Err(MapError::NotPresent { index, entity }),
+                Some(&found) if !occupants.contains(&entity) => {
+                    return Err(MapError::WrongEntity {
+                        index,
+                        expected: entity,
+                        found,
+                    })
+                }
+                _ => {}
+            }
         }
 
-        true
+        let entities = self.entities.get_mut(&layer).unwrap();
+
+        for index in indices {
+            if let Some(occupants) = entities.get_mut(index) {
+                occupants.retain(|e| *e != entity);
+
+                if occupants.is_empty() {
+                    entities.remove(index);
+                }
+            }
+        }
+
+        Ok(())
     }
 
-    pub fn remove_entity_from_square(&mut self, index: usize, size: u32, entity: usize) -> bool {
-        self.assert_inside(index);
-        execute_function_on_square(self.size, index, size, |i: usize| {
-            self.remove_entity(i, entity)
-        })
+    pub fn add_entity(
+        &mut self,
+        index: usize,
+        layer: Layer,
+        entity: usize,
+    ) -> Result<(), MapError> {
+        self.add_entity_to_indices(&[index], layer, entity)
     }
 
-    pub fn add_entity(&mut self, index: usize, entity: usize) -> bool {
-        self.assert_inside(index);
+    pub fn add_entity_to_square(
+        &mut self,
+        index: usize,
+        size: u32,
+        layer: Layer,
+        entity: usize,
+    ) -> Result<(), MapError> {
+        let indices = self.get_square(index, size)?;
+        self.add_entity_to_indices(&indices, layer, entity)
+    }
 
-        match self.entities.insert(index, entity) {
-            Some(other) if other != entity => panic!(
-                "Adding entity {} blocked by {} at {}!",
-                entity, other, index
-            ),
-            _ => {}
+    pub fn add_entity_to_indices(
+        &mut self,
+        indices: &[usize],
+        layer: Layer,
+        entity: usize,
+    ) -> Result<(), MapError> {
+        for &index in indices {
+            self.check_inside(index)?;
+
+            if let Some(by) = self.get_blocker(index, layer, entity) {
+                return Err(MapError::Blocked { index, by });
+            }
+        }
+
+        let entities = self.entities.get_mut(&layer).unwrap();
+
+        for &index in indices {
+            let occupants = entities.entry(index).or_default();
+
+            if !occupants.contains(&entity) {
+                occupants.push(entity);
+            }
         }
 
-        true
+        Ok(())
     }
 
-    pub fn add_entity_to_square(&mut self, index: usize, size: u32, entity: usize) -> bool {
-        self.assert_inside(index);
-        execute_function_on_square(self.size, index, size, |i: usize| {
-            self.add_entity(i, entity)
-        })
+    fn get_square(&self, index: usize, size: u32) -> Result<Vec<usize>, MapError> {
+        self.check_inside(index)?;
+        get_square_indices(self.size, index, size).ok_or(MapError::OutOfBounds(index))
     }
 }
 
-fn execute_function_on_square<F>(map_size: Point, index: usize, size: u32, mut func: F) -> bool
-where
-    F: FnMut(usize) -> bool,
-{
+fn render_tile(renderer: &mut dyn TileRenderer, pos: Point, tile: TileType, color: Color) {
+    match tile {
+        TileType::Floor => {
+            renderer.add_ascii(pos, b'.', color);
+        }
+        TileType::Wall => {
+            renderer.add_ascii(pos, b'#', color);
+        }
+        TileType::Stairs => {
+            renderer.add_ascii(pos, b'>', color);
+        }
+        TileType::DoorOpen => {
+            renderer.add_ascii(pos, b'\'', color);
+        }
+        TileType::DoorClosed => {
+            renderer.add_ascii(pos, b'+', color);
+        }
+        TileType::Rubble => {
+            renderer.add_ascii(pos, b',', color);
+        }
+        TileType::Lever => {
+            renderer.add_ascii(pos, b'/', color);
+        }
+        TileType::Lava => {
+            renderer.add_ascii(pos, b'=', color);
+        }
+        TileType::Water => {
+            renderer.add_ascii(pos, b'~', color);
+        }
+        TileType::DeepWater => {
+            renderer.add_ascii(pos, b'"', color);
+        }
+        TileType::Chasm => {
+            renderer.add_ascii(pos, b':', color);
+        }
+        TileType::Cliff => {
+            renderer.add_ascii(pos, b'^', color);
+        }
+    }
+}
+
+fn get_square_indices(map_size: Point, index: usize, size: u32) -> Option<Vec<usize>> {
     let pos = get_point(index, map_size);
 
-    if pos.x > map_size.x - size || pos.y > map_size.y - size {
-        return false;
+    if pos.x + size > map_size.x || pos.y + size > map_size.y {
+        return None;
     }
 
-    for dx in 0..size {
-        let x = pos.x + dx;
+    let mut indices = Vec::new();
 
-        for dy in 0..size {
-            let y = pos.y + dy;
-
-            if !func(get_index(x, y, map_size)) {
-                return false;
-            }
+    for y in pos.y..(pos.y + size) {
+        for x in pos.x..(pos.x + size) {
+            indices.push(get_index(x, y, map_size));
         }
     }
 
-    true
+    Some(indices)
 }
 
 #[cfg(test)]
@@ -208,6 +567,34 @@ mod tests {
     fn test_is_walkable() {
         assert_eq!(TileType::Floor.is_walkable(), true);
         assert_eq!(TileType::Wall.is_walkable(), false);
+        assert!(TileType::Stairs.is_walkable());
+        assert!(TileType::DoorOpen.is_walkable());
+        assert!(!TileType::DoorClosed.is_walkable());
+        assert!(TileType::Rubble.is_walkable());
+        assert!(TileType::Lever.is_walkable());
+        assert!(TileType::Lava.is_walkable());
+        assert!(TileType::Water.is_walkable());
+        assert!(!TileType::DeepWater.is_walkable());
+        assert!(!TileType::Chasm.is_walkable());
+        assert!(!TileType::Cliff.is_walkable());
+    }
+
+    #[test]
+    fn test_blocks_sight() {
+        assert!(!TileType::Floor.blocks_sight());
+        assert!(TileType::Wall.blocks_sight());
+        assert!(!TileType::Stairs.blocks_sight());
+        assert!(!TileType::DoorOpen.blocks_sight());
+        assert!(TileType::DoorClosed.blocks_sight());
+        assert!(!TileType::Rubble.blocks_sight());
+        assert!(!TileType::Lever.blocks_sight());
+    }
+
+    #[test]
+    fn test_get_movement_cost() {
+        assert_eq!(TileType::Floor.get_movement_cost(), 1);
+        assert_eq!(TileType::Rubble.get_movement_cost(), 2);
+        assert_eq!(TileType::Water.get_movement_cost(), 2);
     }
 
     // map
@@ -219,6 +606,65 @@ mod tests {
         assert_eq!(map.get_size(), SIZE);
     }
 
+    #[test]
+    fn test_get_tile() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), Wall)
+            .build();
+
+        assert_eq!(map.get_tile(0), Floor);
+        assert_eq!(map.get_tile(1), Wall);
+    }
+
+    #[test]
+    fn test_get_neighbors() {
+        let square = TileMapBuilder::new(SIZE, Floor).build();
+        let diagonal = TileMapBuilder::new(SIZE, Floor)
+            .set_topology(Topology::Square8)
+            .build();
+        let hex = TileMapBuilder::new(SIZE, Floor)
+            .set_topology(Topology::Hex)
+            .build();
+
+        assert_eq!(square.get_neighbors(5), vec![9, 6, 1, 4]);
+        assert_eq!(square.get_neighbors(0), vec![4, 1]);
+        assert_eq!(diagonal.get_neighbors(0), vec![4, 5, 1]);
+        assert_eq!(hex.get_neighbors(5), vec![10, 6, 2, 1, 4, 9]);
+        assert_eq!(hex.get_neighbors(4), vec![9, 5, 1, 0, 8]);
+    }
+
+    #[test]
+    fn test_get_distance() {
+        let square = TileMapBuilder::new(SIZE, Floor).build();
+        let diagonal = TileMapBuilder::new(SIZE, Floor)
+            .set_topology(Topology::Square8)
+            .build();
+        let hex = TileMapBuilder::new(SIZE, Floor)
+            .set_topology(Topology::Hex)
+            .build();
+
+        assert_eq!(square.get_distance(0, 10), 4);
+        assert_eq!(diagonal.get_distance(0, 10), 2);
+        assert_eq!(hex.get_distance(0, 10), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 12 is outside the map!")]
+    fn test_get_tile_outside() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+
+        map.get_tile(OUTSIDE);
+    }
+
+    #[test]
+    fn test_is_inside() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+
+        assert!(map.is_inside(0));
+        assert!(map.is_inside(OUTSIDE - 1));
+        assert!(!map.is_inside(OUTSIDE));
+    }
+
     #[test]
     fn test_is_free() {
         let map = TileMapBuilder::new(SIZE, Floor)
@@ -226,29 +672,69 @@ mod tests {
             .build();
 
         for i in 0..12 {
-            assert_eq!(map.is_free(i, 0), i != 0);
+            assert_eq!(map.is_free(i, Layer::Walker, 0), Ok(i != 0));
         }
     }
 
     #[test]
     fn test_is_free_with_occupied_map() {
-        let map = TileMap {
-            size: xy(1, 1),
-            tiles: vec![Floor],
-            entities: vec![(0usize, 0usize)].into_iter().collect(),
-        };
+        let mut map = TileMapBuilder::new(xy(1, 1), Floor).build();
+        map.add_entity(0, Layer::Walker, 0).unwrap();
 
-        assert_eq!(map.is_free(0, 0), true);
-        assert_eq!(map.is_free(0, 1), false);
-        assert_eq!(map.is_free(0, 2), false);
+        assert_eq!(map.is_free(0, Layer::Walker, 0), Ok(true));
+        assert_eq!(map.is_free(0, Layer::Walker, 1), Ok(false));
+        assert_eq!(map.is_free(0, Layer::Walker, 2), Ok(false));
     }
 
     #[test]
-    #[should_panic(expected = "Index 12 is outside the map!")]
     fn test_is_free_outside() {
         let map = TileMapBuilder::new(SIZE, Floor).build();
 
-        map.is_free(OUTSIDE, 0);
+        assert_eq!(
+            map.is_free(OUTSIDE, Layer::Walker, 0),
+            Err(MapError::OutOfBounds(12))
+        );
+    }
+
+    #[test]
+    fn test_is_free_with_mobility() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(0, 0), Wall)
+            .set_tile(xy(1, 0), Chasm)
+            .build();
+        let ghost = Mobility::new(&[MovementMode::Walk, MovementMode::Phase]);
+        let dragon = Mobility::new(&[MovementMode::Fly]);
+
+        assert_eq!(
+            map.is_free_with_mobility(0, Layer::Walker, ghost, 0),
+            Ok(true)
+        );
+        assert_eq!(
+            map.is_free_with_mobility(1, Layer::Walker, ghost, 0),
+            Ok(false)
+        );
+        assert_eq!(
+            map.is_free_with_mobility(0, Layer::Flyer, dragon, 0),
+            Ok(false)
+        );
+        assert_eq!(
+            map.is_free_with_mobility(1, Layer::Flyer, dragon, 0),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn test_is_square_free_with_mobility() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 1), DeepWater)
+            .build();
+        let swimmer = Mobility::new(&[MovementMode::Walk, MovementMode::Swim]);
+
+        assert_eq!(map.is_square_free(0, 2, Layer::Walker, 0), Ok(false));
+        assert_eq!(
+            map.is_square_free_with_mobility(0, 2, Layer::Walker, swimmer, 0),
+            Ok(true)
+        );
     }
 
     #[test]
@@ -269,27 +755,26 @@ mod tests {
 
     #[test]
     fn test_is_square_free_with_occupied_map() {
-        let map = TileMap {
-            size: xy(4, 3),
-            tiles: vec![Floor; 12],
-            entities: vec![(5usize, 0usize)].into_iter().collect(),
-        };
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        map.add_entity(5, Layer::Walker, 0).unwrap();
 
         assert_is_square_free(&map, 0, FREE_RESULTS);
         assert_is_square_free(&map, 1, BLOCKED_RESULTS);
     }
 
     #[test]
-    #[should_panic(expected = "Index 12 is outside the map!")]
     fn test_is_square_free_outside() {
         let map = TileMapBuilder::new(SIZE, Floor).build();
 
-        map.is_square_free(OUTSIDE, 2, 0);
+        assert_eq!(
+            map.is_square_free(OUTSIDE, 2, Layer::Walker, 0),
+            Err(MapError::OutOfBounds(12))
+        );
     }
 
     fn assert_is_square_free(map: &TileMap, entity: usize, results: [bool; 12]) -> () {
         for (i, result) in results.iter().enumerate() {
-            assert_eq!(map.is_square_free(i, 2, entity), *result);
+            assert_eq!(map.is_square_free(i, 2, Layer::Walker, entity), Ok(*result));
         }
     }
 
@@ -298,104 +783,337 @@ mod tests {
     fn test_get_entity_outside() {
         let map = TileMapBuilder::new(SIZE, Floor).build();
 
-        map.get_entity(OUTSIDE);
+        map.get_entity(OUTSIDE, Layer::Walker);
     }
 
     #[test]
     fn test_add_entity() {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
 
-        map.add_entity(5, 42);
+        assert_eq!(map.add_entity(5, Layer::Walker, 42), Ok(()));
 
-        assert_eq!(map.get_entity(0), None);
-        assert_eq!(map.get_entity(5), Some(&42));
+        assert_eq!(map.get_entity(0, Layer::Walker), None);
+        assert_eq!(map.get_entity(5, Layer::Walker), Some(&42));
     }
 
     #[test]
-    #[should_panic(expected = "Index 12 is outside the map!")]
     fn test_add_entity_outside() {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
 
-        map.add_entity(OUTSIDE, 42);
+        assert_eq!(
+            map.add_entity(OUTSIDE, Layer::Walker, 42),
+            Err(MapError::OutOfBounds(12))
+        );
     }
 
     #[test]
     fn test_add_entity_twice() {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
 
-        map.add_entity(2, 42);
-        map.add_entity(2, 42);
+        assert_eq!(map.add_entity(2, Layer::Walker, 42), Ok(()));
+        assert_eq!(map.add_entity(2, Layer::Walker, 42), Ok(()));
 
-        assert_eq!(map.get_entity(0), None);
-        assert_eq!(map.get_entity(2), Some(&42));
+        assert_eq!(map.get_entity(0, Layer::Walker), None);
+        assert_eq!(map.get_entity(2, Layer::Walker), Some(&42));
     }
 
     #[test]
-    #[should_panic(expected = "Adding entity 2 blocked by 1 at 5!")]
     fn test_add_entity_different() {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
 
-        map.add_entity(5, 1);
-        map.add_entity(5, 2);
+        map.add_entity(5, Layer::Walker, 1).unwrap();
+
+        assert_eq!(
+            map.add_entity(5, Layer::Walker, 2),
+            Err(MapError::Blocked { index: 5, by: 1 })
+        );
+        assert_eq!(map.get_entity(5, Layer::Walker), Some(&1));
     }
 
     #[test]
     fn test_remove_entity() {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
 
-        map.add_entity(5, 10);
-        map.remove_entity(5, 10);
-        map.add_entity(5, 20);
+        map.add_entity(5, Layer::Walker, 10).unwrap();
+        assert_eq!(map.remove_entity(5, Layer::Walker, 10), Ok(()));
+        map.add_entity(5, Layer::Walker, 20).unwrap();
 
-        assert_eq!(map.get_entity(5), Some(&20));
+        assert_eq!(map.get_entity(5, Layer::Walker), Some(&20));
     }
 
     #[test]
-    #[should_panic(expected = "Could not remove entity 10 at 5!")]
     fn test_remove_entity_not_there() {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
 
-        map.remove_entity(5, 10);
+        assert_eq!(
+            map.remove_entity(5, Layer::Walker, 10),
+            Err(MapError::NotPresent {
+                index: 5,
+                entity: 10
+            })
+        );
     }
 
     #[test]
-    #[should_panic(expected = "Removed entity 10 instead of 20 at 5!")]
     fn test_remove_wrong_entity() {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
 
-        map.add_entity(5, 10);
-        map.remove_entity(5, 20);
+        map.add_entity(5, Layer::Walker, 10).unwrap();
+
+        assert_eq!(
+            map.remove_entity(5, Layer::Walker, 20),
+            Err(MapError::WrongEntity {
+                index: 5,
+                expected: 20,
+                found: 10
+            })
+        );
+        assert_eq!(map.get_entity(5, Layer::Walker), Some(&10));
     }
 
     #[test]
-    fn test_add_entity_to_square() {
+    fn test_remove_entity_outside() {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
 
-        map.add_entity_to_square(5, 2, 9);
+        assert_eq!(
+            map.remove_entity(OUTSIDE, Layer::Walker, 10),
+            Err(MapError::OutOfBounds(12))
+        );
+    }
+
+    #[test]
+    fn test_add_entity_to_square() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
 
-        assert_eq!(map.get_entity(0), None);
-        assert_eq!(map.get_entity(1), None);
-        assert_eq!(map.get_entity(2), None);
-        assert_eq!(map.get_entity(3), None);
-        assert_eq!(map.get_entity(4), None);
-        assert_eq!(map.get_entity(5), Some(&9));
-        assert_eq!(map.get_entity(6), Some(&9));
-        assert_eq!(map.get_entity(7), None);
-        assert_eq!(map.get_entity(8), None);
-        assert_eq!(map.get_entity(9), Some(&9));
-        assert_eq!(map.get_entity(10), Some(&9));
-        assert_eq!(map.get_entity(11), None);
+        assert_eq!(map.add_entity_to_square(5, 2, Layer::Walker, 9), Ok(()));
+
+        assert_eq!(map.get_entity(0, Layer::Walker), None);
+        assert_eq!(map.get_entity(1, Layer::Walker), None);
+        assert_eq!(map.get_entity(2, Layer::Walker), None);
+        assert_eq!(map.get_entity(3, Layer::Walker), None);
+        assert_eq!(map.get_entity(4, Layer::Walker), None);
+        assert_eq!(map.get_entity(5, Layer::Walker), Some(&9));
+        assert_eq!(map.get_entity(6, Layer::Walker), Some(&9));
+        assert_eq!(map.get_entity(7, Layer::Walker), None);
+        assert_eq!(map.get_entity(8, Layer::Walker), None);
+        assert_eq!(map.get_entity(9, Layer::Walker), Some(&9));
+        assert_eq!(map.get_entity(10, Layer::Walker), Some(&9));
+        assert_eq!(map.get_entity(11, Layer::Walker), None);
     }
 
     #[test]
     fn test_remove_entity_from_square() {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
 
-        map.add_entity_to_square(5, 2, 9);
-        map.remove_entity_from_square(5, 2, 9);
+        map.add_entity_to_square(5, 2, Layer::Walker, 9).unwrap();
+        assert_eq!(
+            map.remove_entity_from_square(5, 2, Layer::Walker, 9),
+            Ok(())
+        );
+
+        for i in 0..12 {
+            assert_eq!(map.get_entity(i, Layer::Walker), None);
+        }
+    }
+
+    #[test]
+    fn test_add_entity_to_square_is_atomic() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        map.add_entity(10, Layer::Walker, 1).unwrap();
 
+        assert_eq!(
+            map.add_entity_to_square(5, 2, Layer::Walker, 9),
+            Err(MapError::Blocked { index: 10, by: 1 })
+        );
         for i in 0..12 {
-            assert_eq!(map.get_entity(i), None);
+            if i != 10 {
+                assert_eq!(map.get_entity(i, Layer::Walker), None);
+            }
         }
     }
+
+    #[test]
+    fn test_add_entity_to_square_outside() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        assert_eq!(
+            map.add_entity_to_square(3, 2, Layer::Walker, 9),
+            Err(MapError::OutOfBounds(3))
+        );
+        assert_eq!(map.get_entity(3, Layer::Walker), None);
+    }
+
+    #[test]
+    fn test_remove_entity_from_square_is_atomic() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        map.add_entity_to_square(5, 2, Layer::Walker, 9).unwrap();
+        map.remove_entity(10, Layer::Walker, 9).unwrap();
+
+        assert_eq!(
+            map.remove_entity_from_square(5, 2, Layer::Walker, 9),
+            Err(MapError::NotPresent {
+                index: 10,
+                entity: 9
+            })
+        );
+        assert_eq!(map.get_entity(5, Layer::Walker), Some(&9));
+        assert_eq!(map.get_entity(6, Layer::Walker), Some(&9));
+        assert_eq!(map.get_entity(9, Layer::Walker), Some(&9));
+    }
+
+    // layers
+
+    #[test]
+    fn test_layers_share_tile() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        map.add_entity(5, Layer::Ground, 1).unwrap();
+        map.add_entity(5, Layer::Walker, 2).unwrap();
+        map.add_entity(5, Layer::Flyer, 3).unwrap();
+
+        assert_eq!(map.get_entity(5, Layer::Walker), Some(&2));
+        assert_eq!(map.get_entity(5, Layer::Ground), Some(&1));
+        assert_eq!(map.get_entity(5, Layer::Flyer), Some(&3));
+        assert_eq!(
+            map.get_entities(5),
+            vec![(Layer::Ground, 1), (Layer::Walker, 2), (Layer::Flyer, 3)]
+        );
+        assert_eq!(map.get_entities_on_layer(Layer::Flyer).len(), 1);
+    }
+
+    #[test]
+    fn test_ground_layer_stacks() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        map.add_entity(5, Layer::Ground, 1).unwrap();
+        map.add_entity(5, Layer::Ground, 2).unwrap();
+        map.add_entity(5, Layer::Ground, 3).unwrap();
+        map.add_entity(5, Layer::Walker, 4).unwrap();
+
+        assert_eq!(map.get_occupants(5, Layer::Ground), &[1, 2, 3]);
+        assert_eq!(map.get_entity(5, Layer::Ground), Some(&1));
+        assert_eq!(map.is_free(5, Layer::Ground, 0), Ok(true));
+        assert_eq!(
+            map.add_entity(5, Layer::Walker, 5),
+            Err(MapError::Blocked { index: 5, by: 4 })
+        );
+
+        map.remove_entity(5, Layer::Ground, 2).unwrap();
+
+        assert_eq!(map.get_occupants(5, Layer::Ground), &[1, 3]);
+        assert_eq!(
+            map.remove_entity(5, Layer::Ground, 2),
+            Err(MapError::WrongEntity {
+                index: 5,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(map.get_occupants(6, Layer::Ground), &[] as &[usize]);
+    }
+
+    #[test]
+    fn test_is_free_per_layer() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        map.add_entity(5, Layer::Walker, 2).unwrap();
+
+        assert_eq!(map.is_free(5, Layer::Walker, 0), Ok(false));
+        assert_eq!(map.is_free(5, Layer::Flyer, 0), Ok(true));
+        assert_eq!(map.is_free(5, Layer::Ground, 0), Ok(true));
+        assert_eq!(map.is_square_free(0, 2, Layer::Flyer, 0), Ok(true));
+        assert_eq!(map.is_square_free(0, 2, Layer::Walker, 0), Ok(false));
+    }
+
+    #[test]
+    fn test_get_blockers() {
+        let rules = BlockingRules::default().add(Layer::Walker, Layer::Flyer);
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_blocking_rules(rules)
+            .build();
+
+        map.add_entity(5, Layer::Ground, 1).unwrap();
+        map.add_entity(5, Layer::Walker, 2).unwrap();
+        map.add_entity(5, Layer::Flyer, 3).unwrap();
+
+        assert_eq!(map.get_blockers(5, Layer::Walker, 0), vec![2, 3]);
+        assert_eq!(map.get_blockers(5, Layer::Walker, 2), vec![3]);
+        assert_eq!(map.get_blockers(5, Layer::Flyer, 0), vec![3]);
+        assert_eq!(map.get_blockers(6, Layer::Walker, 0), Vec::new());
+    }
+
+    #[test]
+    fn test_add_entity_blocked_by_rule() {
+        let rules = BlockingRules::default().add(Layer::Flyer, Layer::Walker);
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_blocking_rules(rules)
+            .build();
+
+        map.add_entity(5, Layer::Walker, 2).unwrap();
+
+        assert_eq!(map.is_free(5, Layer::Flyer, 0), Ok(false));
+        assert_eq!(
+            map.add_entity(5, Layer::Flyer, 3),
+            Err(MapError::Blocked { index: 5, by: 2 })
+        );
+        assert_eq!(map.add_entity(5, Layer::Ground, 3), Ok(()));
+    }
+
+    #[test]
+    fn test_remove_entity_per_layer() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        map.add_entity(5, Layer::Walker, 2).unwrap();
+        map.add_entity_to_square(5, 2, Layer::Flyer, 3).unwrap();
+
+        assert_eq!(
+            map.remove_entity(5, Layer::Flyer, 2),
+            Err(MapError::WrongEntity {
+                index: 5,
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(map.remove_entity_from_square(5, 2, Layer::Flyer, 3), Ok(()));
+        assert_eq!(map.get_entities(5), vec![(Layer::Walker, 2)]);
+    }
+
+    #[test]
+    fn test_display_error() {
+        assert_eq!(
+            MapError::OutOfBounds(12).to_string(),
+            "Index 12 is outside the map!"
+        );
+        assert_eq!(
+            MapError::Blocked { index: 5, by: 1 }.to_string(),
+            "Index 5 is blocked by entity 1!"
+        );
+        assert_eq!(
+            MapError::NotPresent {
+                index: 5,
+                entity: 10
+            }
+            .to_string(),
+            "Entity 10 is not at 5!"
+        );
+        assert_eq!(
+            MapError::WrongEntity {
+                index: 5,
+                expected: 20,
+                found: 10
+            }
+            .to_string(),
+            "Found entity 10 instead of 20 at 5!"
+        );
+        assert_eq!(
+            MapError::WrongTile {
+                index: 5,
+                found: TileType::Wall
+            }
+            .to_string(),
+            "Tile Wall at 5 does not support this!"
+        );
+    }
 }
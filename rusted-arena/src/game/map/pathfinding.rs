@@ -0,0 +1,235 @@
+use super::*;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path {
+    pub indices: Vec<usize>,
+    pub cost: u32,
+}
+
+pub fn find_path<F>(
+    map: &TileMap,
+    start: usize,
+    goal: usize,
+    max_nodes: usize,
+    min_cost: u32,
+    get_cost: F,
+) -> Option<Path>
+where
+    F: Fn(usize) -> Option<u32>,
+{
+    map.assert_inside(start);
+    map.assert_inside(goal);
+
+    let mut open = BinaryHeap::new();
+    let mut costs: HashMap<usize, u32> = HashMap::new();
+    let mut previous: HashMap<usize, usize> = HashMap::new();
+    let mut expanded = 0;
+
+    costs.insert(start, 0);
+    open.push(Reverse((
+        map.get_distance(start, goal) * min_cost,
+        0,
+        start,
+    )));
+
+    while let Some(Reverse((_, cost, index))) = open.pop() {
+        if cost > costs[&index] {
+            continue;
+        }
+
+        if index == goal {
+            return Some(create_path(&previous, start, goal, costs[&goal]));
+        }
+
+        expanded += 1;
+
+        if expanded > max_nodes {
+            return None;
+        }
+
+        for neighbor in map.get_neighbors(index) {
+            if let Some(step_cost) = get_cost(neighbor) {
+                let new_cost = cost + step_cost;
+
+                if costs.get(&neighbor).is_none_or(|c| new_cost < *c) {
+                    costs.insert(neighbor, new_cost);
+                    previous.insert(neighbor, index);
+                    let estimate = new_cost + map.get_distance(neighbor, goal) * min_cost;
+                    open.push(Reverse((estimate, new_cost, neighbor)));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn create_path(previous: &HashMap<usize, usize>, start: usize, goal: usize, cost: u32) -> Path {
+    let mut indices = Vec::new();
+    let mut index = goal;
+
+    while index != start {
+        indices.push(index);
+        index = previous[&index];
+    }
+
+    indices.reverse();
+
+    Path { indices, cost }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::TileType::*;
+
+    const SIZE: Point = Point { x: 4, y: 3 };
+
+    fn get_cost(map: &TileMap, index: usize) -> Option<u32> {
+        let tile = map.get_tile(index);
+
+        if tile.is_walkable() {
+            Some(tile.get_movement_cost())
+        } else {
+            None
+        }
+    }
+
+    #[test]
+    fn test_straight_path() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+
+        let path = find_path(&map, 0, 3, 100, 1, |i| get_cost(&map, i));
+
+        assert_eq!(
+            path,
+            Some(Path {
+                indices: vec![1, 2, 3],
+                cost: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_path_around_wall() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), Wall)
+            .set_tile(xy(1, 1), Wall)
+            .build();
+
+        let path = find_path(&map, 0, 2, 100, 1, |i| get_cost(&map, i)).unwrap();
+
+        assert_eq!(path.indices, vec![4, 8, 9, 10, 6, 2]);
+        assert_eq!(path.cost, 6);
+    }
+
+    #[test]
+    fn test_path_to_start() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+
+        let path = find_path(&map, 5, 5, 100, 1, |i| get_cost(&map, i));
+
+        assert_eq!(
+            path,
+            Some(Path {
+                indices: Vec::new(),
+                cost: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_unreachable_goal() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), Wall)
+            .set_tile(xy(1, 1), Wall)
+            .set_tile(xy(1, 2), Wall)
+            .build();
+
+        assert_eq!(find_path(&map, 0, 3, 100, 1, |i| get_cost(&map, i)), None);
+    }
+
+    #[test]
+    fn test_custom_costs() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+
+        let path = find_path(
+            &map,
+            0,
+            2,
+            100,
+            1,
+            |i| if i == 1 { Some(5) } else { Some(1) },
+        )
+        .unwrap();
+
+        assert_eq!(path.indices, vec![4, 5, 6, 2]);
+        assert_eq!(path.cost, 4);
+    }
+
+    #[test]
+    fn test_zero_costs() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+        let get_cost = |i: usize| if i < 4 { Some(1) } else { Some(0) };
+
+        let path = find_path(&map, 0, 3, 100, 0, get_cost).unwrap();
+
+        assert_eq!(path.indices, vec![4, 5, 6, 7, 3]);
+        assert_eq!(path.cost, 1);
+    }
+
+    #[test]
+    fn test_node_limit_ignores_stale_entries() {
+        let map = TileMapBuilder::new(Point { x: 5, y: 5 }, Floor).build();
+        let get_cost = |i: usize| if i < 10 { Some(5) } else { Some(1) };
+
+        let path = find_path(&map, 20, 9, 25, 1, get_cost).unwrap();
+
+        assert_eq!(path.cost, 11);
+    }
+
+    #[test]
+    fn test_diagonal_path() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_topology(Topology::Square8)
+            .build();
+
+        let path = find_path(&map, 0, 11, 100, 1, |i| get_cost(&map, i)).unwrap();
+
+        assert_eq!(path.cost, 3);
+    }
+
+    #[test]
+    fn test_hex_path() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_topology(Topology::Hex)
+            .build();
+
+        let path = find_path(&map, 0, 11, 100, 1, |i| get_cost(&map, i)).unwrap();
+
+        assert_eq!(path.cost, 4);
+
+        for pair in path.indices.windows(2) {
+            assert_eq!(map.get_distance(pair[0], pair[1]), 1);
+        }
+    }
+
+    #[test]
+    fn test_node_limit() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+
+        assert_eq!(find_path(&map, 0, 11, 3, 1, |i| get_cost(&map, i)), None);
+        assert!(find_path(&map, 0, 11, 12, 1, |i| get_cost(&map, i)).is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 12 is outside the map!")]
+    fn test_goal_outside() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+
+        find_path(&map, 0, 12, 100, 1, |i| get_cost(&map, i));
+    }
+}
@@ -0,0 +1,245 @@
+use super::*;
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Metric {
+    Manhattan,
+    Chebyshev,
+    Euclidean,
+}
+
+impl Metric {
+    pub fn get_distance(self, from: Point, to: Point) -> f32 {
+        let delta_x = (from.x as i32 - to.x as i32).abs() as f32;
+        let delta_y = (from.y as i32 - to.y as i32).abs() as f32;
+
+        match self {
+            Metric::Manhattan => delta_x + delta_y,
+            Metric::Chebyshev => delta_x.max(delta_y),
+            Metric::Euclidean => (delta_x * delta_x + delta_y * delta_y).sqrt(),
+        }
+    }
+}
+
+impl TileMap {
+    pub fn get_zone_of_control(&self, footprint: &[usize]) -> Vec<usize> {
+        let mut zone: Vec<usize> = footprint
+            .iter()
+            .flat_map(|index| self.get_neighbors(*index))
+            .filter(|index| !footprint.contains(index))
+            .collect();
+
+        zone.sort_unstable();
+        zone.dedup();
+        zone
+    }
+
+    pub fn get_entities_in_radius<F>(
+        &self,
+        index: usize,
+        radius: u32,
+        metric: Metric,
+        filter: F,
+    ) -> Vec<usize>
+    where
+        F: Fn(usize) -> bool,
+    {
+        self.assert_inside(index);
+
+        let center = get_point(index, self.size);
+
+        self.get_entities_where(filter, |i| {
+            metric.get_distance(center, get_point(i, self.size)) <= radius as f32
+        })
+    }
+
+    pub fn get_entities_in_rectangle<F>(&self, start: Point, size: Point, filter: F) -> Vec<usize>
+    where
+        F: Fn(usize) -> bool,
+    {
+        let end = start + size;
+
+        self.get_entities_where(filter, |i| {
+            let pos = get_point(i, self.size);
+            pos.x >= start.x && pos.x < end.x && pos.y >= start.y && pos.y < end.y
+        })
+    }
+
+    pub fn find_nearest_entity<F>(&self, index: usize, metric: Metric, filter: F) -> Option<usize>
+    where
+        F: Fn(usize) -> bool,
+    {
+        self.assert_inside(index);
+
+        let center = get_point(index, self.size);
+        let mut nearest: Option<(f32, usize)> = None;
+
+        for (&i, &entity) in self.get_occupied_indices() {
+            if !filter(entity) {
+                continue;
+            }
+
+            let distance = metric.get_distance(center, get_point(i, self.size));
+
+            let is_nearer = match nearest {
+                None => true,
+                Some((best_distance, best_entity)) => {
+                    distance < best_distance || (distance == best_distance && entity < best_entity)
+                }
+            };
+
+            if is_nearer {
+                nearest = Some((distance, entity));
+            }
+        }
+
+        nearest.map(|(_, entity)| entity)
+    }
+
+    fn get_entities_where<F, P>(&self, filter: F, is_inside: P) -> Vec<usize>
+    where
+        F: Fn(usize) -> bool,
+        P: Fn(usize) -> bool,
+    {
+        let mut entities: Vec<usize> = self
+            .get_occupied_indices()
+            .filter(|(i, _)| is_inside(**i))
+            .map(|(_, entity)| *entity)
+            .filter(|entity| filter(*entity))
+            .collect();
+
+        entities.sort_unstable();
+        entities.dedup();
+        entities
+    }
+
+    fn get_occupied_indices(&self) -> impl Iterator<Item = (&usize, &usize)> {
+        self.entities.values().flat_map(|occupants| {
+            occupants
+                .iter()
+                .flat_map(|(i, entities)| entities.iter().map(move |entity| (i, entity)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Metric::*;
+    use super::*;
+    use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::TileType::*;
+
+    const SIZE: Point = Point { x: 5, y: 5 };
+
+    fn create_map() -> TileMap {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        map.add_entity(get_index(2, 2, SIZE), Layer::Walker, 1)
+            .unwrap();
+        map.add_entity(get_index(3, 3, SIZE), Layer::Walker, 2)
+            .unwrap();
+        map.add_entity(get_index(4, 0, SIZE), Layer::Walker, 3)
+            .unwrap();
+        map.add_entity_to_square(get_index(0, 3, SIZE), 2, Layer::Walker, 4)
+            .unwrap();
+        map
+    }
+
+    #[test]
+    fn test_get_zone_of_control() {
+        let map = TileMapBuilder::new(xy(4, 4), Floor).build();
+
+        assert_eq!(map.get_zone_of_control(&[0]), vec![1, 4]);
+        assert_eq!(
+            map.get_zone_of_control(&[5, 6, 9, 10]),
+            vec![1, 2, 4, 7, 8, 11, 13, 14]
+        );
+    }
+
+    #[test]
+    fn test_get_zone_of_control_with_topology() {
+        let map = TileMapBuilder::new(xy(4, 4), Floor)
+            .set_topology(Topology::Square8)
+            .build();
+
+        assert_eq!(map.get_zone_of_control(&[0]), vec![1, 4, 5]);
+    }
+
+    #[test]
+    fn test_get_distance() {
+        let from = xy(1, 1);
+        let to = xy(4, 5);
+
+        assert_eq!(Manhattan.get_distance(from, to), 7.0);
+        assert_eq!(Chebyshev.get_distance(from, to), 4.0);
+        assert_eq!(Euclidean.get_distance(from, to), 5.0);
+    }
+
+    #[test]
+    fn test_get_entities_in_radius() {
+        let map = create_map();
+        let center = get_index(2, 2, SIZE);
+
+        assert_eq!(
+            map.get_entities_in_radius(center, 1, Manhattan, |_| true),
+            vec![1]
+        );
+        assert_eq!(
+            map.get_entities_in_radius(center, 1, Chebyshev, |_| true),
+            vec![1, 2, 4]
+        );
+        assert_eq!(
+            map.get_entities_in_radius(center, 2, Euclidean, |_| true),
+            vec![1, 2, 4]
+        );
+        assert_eq!(
+            map.get_entities_in_radius(center, 2, Chebyshev, |_| true),
+            vec![1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_get_entities_in_radius_with_filter() {
+        let map = create_map();
+        let center = get_index(2, 2, SIZE);
+
+        assert_eq!(
+            map.get_entities_in_radius(center, 2, Chebyshev, |e| e % 2 == 0),
+            vec![2, 4]
+        );
+    }
+
+    #[test]
+    fn test_get_entities_in_rectangle() {
+        let map = create_map();
+
+        assert_eq!(
+            map.get_entities_in_rectangle(xy(1, 2), xy(3, 3), |_| true),
+            vec![1, 2, 4]
+        );
+        assert_eq!(
+            map.get_entities_in_rectangle(xy(0, 0), xy(2, 2), |_| true),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn test_find_nearest_entity() {
+        let map = create_map();
+
+        assert_eq!(
+            map.find_nearest_entity(get_index(4, 1, SIZE), Manhattan, |_| true),
+            Some(3)
+        );
+        assert_eq!(
+            map.find_nearest_entity(get_index(0, 0, SIZE), Chebyshev, |_| true),
+            Some(1)
+        );
+        assert_eq!(
+            map.find_nearest_entity(get_index(0, 0, SIZE), Manhattan, |e| e != 1),
+            Some(4)
+        );
+        assert_eq!(
+            map.find_nearest_entity(get_index(0, 0, SIZE), Chebyshev, |_| false),
+            None
+        );
+    }
+}
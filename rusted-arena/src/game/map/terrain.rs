@@ -0,0 +1,332 @@
+use super::*;
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct TileChange {
+    pub index: usize,
+    pub from: TileType,
+    pub to: TileType,
+}
+
+impl TileMap {
+    pub fn set_tile(&mut self, index: usize, tile: TileType) -> Result<(), MapError> {
+        self.check_tile_change(index, tile)?;
+        self.change_tile(index, tile);
+        Ok(())
+    }
+
+    pub fn open_door(&mut self, index: usize) -> Result<(), MapError> {
+        self.check_door(index)?;
+        self.set_tile(index, TileType::DoorOpen)
+    }
+
+    pub fn close_door(&mut self, index: usize) -> Result<(), MapError> {
+        self.check_door(index)?;
+        self.set_tile(index, TileType::DoorClosed)
+    }
+
+    pub fn toggle_door(&mut self, index: usize) -> Result<(), MapError> {
+        let tile = self.get_toggled_door(index)?;
+        self.set_tile(index, tile)
+    }
+
+    pub fn get_hit_points(&self, index: usize) -> Option<u32> {
+        self.assert_inside(index);
+        self.hit_points.get(&index).copied()
+    }
+
+    pub fn damage_tile(&mut self, index: usize, damage: u32) -> Result<bool, MapError> {
+        self.check_inside(index)?;
+
+        let hit_points = match self.hit_points.get_mut(&index) {
+            None => {
+                return Err(MapError::WrongTile {
+                    index,
+                    found: self.tiles[index],
+                })
+            }
+            Some(hit_points) => hit_points,
+        };
+
+        *hit_points = hit_points.saturating_sub(damage);
+
+        if *hit_points > 0 {
+            return Ok(false);
+        }
+
+        self.hit_points.remove(&index);
+        self.change_tile(index, TileType::Rubble);
+
+        Ok(true)
+    }
+
+    pub fn get_lever_targets(&self, index: usize) -> &[usize] {
+        self.assert_inside(index);
+        self.levers.get(&index).map_or(&[], |targets| targets)
+    }
+
+    pub fn pull_lever(&mut self, index: usize) -> Result<(), MapError> {
+        self.check_inside(index)?;
+
+        if self.tiles[index] != TileType::Lever {
+            return Err(MapError::WrongTile {
+                index,
+                found: self.tiles[index],
+            });
+        }
+
+        let mut toggled = Vec::new();
+
+        for &target in self.get_lever_targets(index) {
+            let tile = self.get_toggled_door(target)?;
+            self.check_tile_change(target, tile)?;
+            toggled.push((target, tile));
+        }
+
+        for (target, tile) in toggled {
+            self.change_tile(target, tile);
+        }
+
+        Ok(())
+    }
+
+    pub fn take_changes(&mut self) -> Vec<TileChange> {
+        std::mem::take(&mut self.changes)
+    }
+
+    fn check_door(&self, index: usize) -> Result<(), MapError> {
+        self.get_toggled_door(index).map(|_| ())
+    }
+
+    fn get_toggled_door(&self, index: usize) -> Result<TileType, MapError> {
+        self.check_inside(index)?;
+
+        match self.tiles[index] {
+            TileType::DoorOpen => Ok(TileType::DoorClosed),
+            TileType::DoorClosed => Ok(TileType::DoorOpen),
+            found => Err(MapError::WrongTile { index, found }),
+        }
+    }
+
+    fn check_tile_change(&self, index: usize, tile: TileType) -> Result<(), MapError> {
+        self.check_inside(index)?;
+
+        if !tile.is_walkable() {
+            if let Some((_, by)) = self.get_entities(index).first() {
+                return Err(MapError::Blocked { index, by: *by });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn change_tile(&mut self, index: usize, to: TileType) {
+        let from = self.tiles[index];
+
+        if from != to {
+            self.tiles[index] = to;
+            self.changes.push(TileChange { index, from, to });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::fov::calculate_fov;
+    use crate::game::map::TileType::*;
+
+    const SIZE: Point = Point { x: 4, y: 3 };
+
+    fn change(index: usize, from: TileType, to: TileType) -> TileChange {
+        TileChange { index, from, to }
+    }
+
+    #[test]
+    fn test_set_tile() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        assert_eq!(map.set_tile(5, Wall), Ok(()));
+        assert_eq!(map.set_tile(6, Floor), Ok(()));
+
+        assert_eq!(map.get_tile(5), Wall);
+        assert_eq!(map.take_changes(), vec![change(5, Floor, Wall)]);
+        assert_eq!(map.take_changes(), Vec::new());
+    }
+
+    #[test]
+    fn test_set_tile_blocked() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        map.add_entity(5, Layer::Ground, 3).unwrap();
+
+        assert_eq!(
+            map.set_tile(5, Wall),
+            Err(MapError::Blocked { index: 5, by: 3 })
+        );
+        assert_eq!(map.set_tile(5, Rubble), Ok(()));
+    }
+
+    #[test]
+    fn test_set_tile_outside() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        assert_eq!(map.set_tile(12, Wall), Err(MapError::OutOfBounds(12)));
+    }
+
+    #[test]
+    fn test_doors() {
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), DoorClosed)
+            .build();
+
+        assert_eq!(map.is_free(1, Layer::Walker, 0), Ok(false));
+        assert_eq!(map.open_door(1), Ok(()));
+        assert_eq!(map.is_free(1, Layer::Walker, 0), Ok(true));
+        assert_eq!(map.toggle_door(1), Ok(()));
+        assert_eq!(map.get_tile(1), DoorClosed);
+        assert_eq!(map.close_door(1), Ok(()));
+
+        assert_eq!(
+            map.take_changes(),
+            vec![
+                change(1, DoorClosed, DoorOpen),
+                change(1, DoorOpen, DoorClosed)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_close_occupied_door() {
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), DoorOpen)
+            .build();
+
+        map.add_entity(1, Layer::Walker, 7).unwrap();
+
+        assert_eq!(
+            map.close_door(1),
+            Err(MapError::Blocked { index: 1, by: 7 })
+        );
+        assert_eq!(map.get_tile(1), DoorOpen);
+    }
+
+    #[test]
+    fn test_open_wall() {
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), Wall)
+            .build();
+
+        assert_eq!(
+            map.open_door(1),
+            Err(MapError::WrongTile {
+                index: 1,
+                found: Wall
+            })
+        );
+    }
+
+    #[test]
+    fn test_door_updates_fov() {
+        let mut map = TileMapBuilder::new(xy(5, 1), Floor)
+            .set_tile(xy(2, 0), DoorClosed)
+            .build();
+
+        assert!(!calculate_fov(&map, 0, 4).contains(&3));
+
+        map.open_door(2).unwrap();
+
+        assert!(calculate_fov(&map, 0, 4).contains(&3));
+    }
+
+    #[test]
+    fn test_damage_tile() {
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), Wall)
+            .set_hit_points(xy(1, 0), 10)
+            .build();
+
+        assert_eq!(map.get_hit_points(1), Some(10));
+        assert_eq!(map.damage_tile(1, 4), Ok(false));
+        assert_eq!(map.get_hit_points(1), Some(6));
+        assert_eq!(map.get_tile(1), Wall);
+
+        assert_eq!(map.damage_tile(1, 8), Ok(true));
+        assert_eq!(map.get_hit_points(1), None);
+        assert_eq!(map.get_tile(1), Rubble);
+        assert_eq!(map.is_free(1, Layer::Walker, 0), Ok(true));
+        assert_eq!(map.take_changes(), vec![change(1, Wall, Rubble)]);
+    }
+
+    #[test]
+    fn test_damage_indestructible_tile() {
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), Wall)
+            .build();
+
+        assert_eq!(
+            map.damage_tile(1, 4),
+            Err(MapError::WrongTile {
+                index: 1,
+                found: Wall
+            })
+        );
+    }
+
+    #[test]
+    fn test_pull_lever() {
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(0, 0), Lever)
+            .set_tile(xy(1, 1), DoorClosed)
+            .set_tile(xy(2, 1), DoorOpen)
+            .connect_lever(xy(0, 0), xy(1, 1))
+            .connect_lever(xy(0, 0), xy(2, 1))
+            .build();
+
+        assert_eq!(map.get_lever_targets(0), &[5, 6]);
+        assert_eq!(map.pull_lever(0), Ok(()));
+
+        assert_eq!(map.get_tile(5), DoorOpen);
+        assert_eq!(map.get_tile(6), DoorClosed);
+        assert_eq!(
+            map.take_changes(),
+            vec![
+                change(5, DoorClosed, DoorOpen),
+                change(6, DoorOpen, DoorClosed)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pull_lever_is_atomic() {
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(0, 0), Lever)
+            .set_tile(xy(1, 1), DoorClosed)
+            .set_tile(xy(2, 1), DoorOpen)
+            .connect_lever(xy(0, 0), xy(1, 1))
+            .connect_lever(xy(0, 0), xy(2, 1))
+            .build();
+
+        map.add_entity(6, Layer::Walker, 7).unwrap();
+
+        assert_eq!(
+            map.pull_lever(0),
+            Err(MapError::Blocked { index: 6, by: 7 })
+        );
+        assert_eq!(map.get_tile(5), DoorClosed);
+        assert_eq!(map.take_changes(), Vec::new());
+    }
+
+    #[test]
+    fn test_pull_no_lever() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        assert_eq!(
+            map.pull_lever(0),
+            Err(MapError::WrongTile {
+                index: 0,
+                found: Floor
+            })
+        );
+    }
+}
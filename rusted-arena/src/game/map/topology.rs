@@ -0,0 +1,283 @@
+use super::line::get_line;
+use super::*;
+
+const SQUARE4_OFFSETS: [(i32, i32); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+const SQUARE8_OFFSETS: [(i32, i32); 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+];
+const HEX_EVEN_OFFSETS: [(i32, i32); 6] = [(0, 1), (1, 0), (0, -1), (-1, -1), (-1, 0), (-1, 1)];
+const HEX_ODD_OFFSETS: [(i32, i32); 6] = [(1, 1), (1, 0), (1, -1), (0, -1), (-1, 0), (0, 1)];
+
+const SQUARE4_DIRECTIONS: [Direction; 4] = [
+    Direction::North,
+    Direction::East,
+    Direction::South,
+    Direction::West,
+];
+const SQUARE8_DIRECTIONS: [Direction; 8] = [
+    Direction::North,
+    Direction::NorthEast,
+    Direction::East,
+    Direction::SouthEast,
+    Direction::South,
+    Direction::SouthWest,
+    Direction::West,
+    Direction::NorthWest,
+];
+const HEX_DIRECTIONS: [Direction; 6] = [
+    Direction::NorthEast,
+    Direction::East,
+    Direction::SouthEast,
+    Direction::SouthWest,
+    Direction::West,
+    Direction::NorthWest,
+];
+
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Topology {
+    Square4,
+    Square8,
+    Hex,
+}
+
+impl Topology {
+    pub fn get_offsets(self, pos: Point) -> &'static [(i32, i32)] {
+        match self {
+            Topology::Square4 => &SQUARE4_OFFSETS,
+            Topology::Square8 => &SQUARE8_OFFSETS,
+            Topology::Hex if pos.y % 2 == 1 => &HEX_ODD_OFFSETS,
+            Topology::Hex => &HEX_EVEN_OFFSETS,
+        }
+    }
+
+    pub fn get_directions(self) -> &'static [Direction] {
+        match self {
+            Topology::Square4 => &SQUARE4_DIRECTIONS,
+            Topology::Square8 => &SQUARE8_DIRECTIONS,
+            Topology::Hex => &HEX_DIRECTIONS,
+        }
+    }
+
+    pub fn get_offset(self, pos: Point, dir: Direction) -> Option<(i32, i32)> {
+        if !self.get_directions().contains(&dir) {
+            return None;
+        }
+
+        let is_odd = pos.y % 2 == 1;
+
+        let offset = match dir {
+            Direction::North => (0, 1),
+            Direction::East => (1, 0),
+            Direction::South => (0, -1),
+            Direction::West => (-1, 0),
+            Direction::NorthEast if self == Topology::Hex => (is_odd as i32, 1),
+            Direction::SouthEast if self == Topology::Hex => (is_odd as i32, -1),
+            Direction::SouthWest if self == Topology::Hex => (is_odd as i32 - 1, -1),
+            Direction::NorthWest if self == Topology::Hex => (is_odd as i32 - 1, 1),
+            Direction::NorthEast => (1, 1),
+            Direction::SouthEast => (1, -1),
+            Direction::SouthWest => (-1, -1),
+            Direction::NorthWest => (-1, 1),
+        };
+
+        Some(offset)
+    }
+
+    pub fn get_distance(self, from: Point, to: Point) -> u32 {
+        match self {
+            Topology::Square4 => {
+                let (delta_x, delta_y) = get_deltas(from, to);
+                delta_x + delta_y
+            }
+            Topology::Square8 => {
+                let (delta_x, delta_y) = get_deltas(from, to);
+                delta_x.max(delta_y)
+            }
+            Topology::Hex => {
+                let (from_q, from_r) = to_axial(from);
+                let (to_q, to_r) = to_axial(to);
+                let delta_q = from_q - to_q;
+                let delta_r = from_r - to_r;
+                ((delta_q.abs() + delta_r.abs() + (delta_q + delta_r).abs()) / 2) as u32
+            }
+        }
+    }
+
+    pub fn get_line(self, from: Point, to: Point) -> Vec<Point> {
+        match self {
+            Topology::Hex => get_hex_line(from, to),
+            _ => get_line(from, to),
+        }
+    }
+
+    pub fn get_render_position(self, pos: Point) -> Point {
+        match self {
+            Topology::Hex => xy(pos.x * 2 + pos.y % 2, pos.y),
+            _ => pos,
+        }
+    }
+}
+
+pub fn to_axial(pos: Point) -> (i32, i32) {
+    let x = pos.x as i32;
+    let y = pos.y as i32;
+    (x - (y - (y & 1)) / 2, y)
+}
+
+pub fn from_axial(q: i32, r: i32) -> (i32, i32) {
+    (q + (r - (r & 1)) / 2, r)
+}
+
+fn get_deltas(from: Point, to: Point) -> (u32, u32) {
+    let delta_x = (from.x as i32 - to.x as i32).abs();
+    let delta_y = (from.y as i32 - to.y as i32).abs();
+    (delta_x as u32, delta_y as u32)
+}
+
+fn get_hex_line(from: Point, to: Point) -> Vec<Point> {
+    let distance = Topology::Hex.get_distance(from, to);
+
+    if distance == 0 {
+        return vec![from];
+    }
+
+    let (from_q, from_r) = to_axial(from);
+    let (to_q, to_r) = to_axial(to);
+    let mut points = Vec::new();
+
+    for step in 0..=distance {
+        let t = step as f32 / distance as f32;
+        let q = lerp(from_q as f32 + 1e-6, to_q as f32 + 1e-6, t);
+        let r = lerp(from_r as f32 + 1e-6, to_r as f32 + 1e-6, t);
+        let (q, r) = round_axial(q, r);
+        let (x, y) = from_axial(q, r);
+        points.push(xy(x as u32, y as u32));
+    }
+
+    points
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
+fn round_axial(q: f32, r: f32) -> (i32, i32) {
+    let s = -q - r;
+    let mut rounded_q = q.round();
+    let mut rounded_r = r.round();
+    let rounded_s = s.round();
+    let delta_q = (rounded_q - q).abs();
+    let delta_r = (rounded_r - r).abs();
+    let delta_s = (rounded_s - s).abs();
+
+    if delta_q > delta_r && delta_q > delta_s {
+        rounded_q = -rounded_r - rounded_s;
+    } else if delta_r > delta_s {
+        rounded_r = -rounded_q - rounded_s;
+    }
+
+    (rounded_q as i32, rounded_r as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Topology::*;
+    use super::*;
+
+    #[test]
+    fn test_get_distance() {
+        assert_eq!(Square4.get_distance(xy(1, 1), xy(3, 4)), 5);
+        assert_eq!(Square8.get_distance(xy(1, 1), xy(3, 4)), 3);
+        assert_eq!(Hex.get_distance(xy(1, 1), xy(1, 1)), 0);
+        assert_eq!(Hex.get_distance(xy(1, 1), xy(2, 1)), 1);
+        assert_eq!(Hex.get_distance(xy(1, 1), xy(2, 2)), 1);
+        assert_eq!(Hex.get_distance(xy(1, 1), xy(1, 3)), 2);
+        assert_eq!(Hex.get_distance(xy(0, 0), xy(3, 4)), 5);
+    }
+
+    #[test]
+    fn test_hex_neighbors_have_distance_one() {
+        for pos in [xy(2, 2), xy(2, 3)].iter() {
+            for (delta_x, delta_y) in Hex.get_offsets(*pos) {
+                let neighbor = xy(
+                    (pos.x as i32 + delta_x) as u32,
+                    (pos.y as i32 + delta_y) as u32,
+                );
+                assert_eq!(Hex.get_distance(*pos, neighbor), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_offset() {
+        assert_eq!(Square4.get_offset(xy(2, 2), Direction::North), Some((0, 1)));
+        assert_eq!(Square4.get_offset(xy(2, 2), Direction::NorthEast), None);
+        assert_eq!(
+            Square8.get_offset(xy(2, 2), Direction::SouthWest),
+            Some((-1, -1))
+        );
+        assert_eq!(Hex.get_offset(xy(2, 2), Direction::North), None);
+        assert_eq!(Hex.get_offset(xy(2, 2), Direction::NorthEast), Some((0, 1)));
+        assert_eq!(Hex.get_offset(xy(2, 3), Direction::NorthEast), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_directions_match_offsets() {
+        for topology in [Square4, Square8, Hex].iter() {
+            for pos in [xy(2, 2), xy(2, 3)].iter() {
+                let mut from_directions: Vec<(i32, i32)> = topology
+                    .get_directions()
+                    .iter()
+                    .filter_map(|dir| topology.get_offset(*pos, *dir))
+                    .collect();
+                let mut offsets = topology.get_offsets(*pos).to_vec();
+
+                from_directions.sort_unstable();
+                offsets.sort_unstable();
+
+                assert_eq!(from_directions, offsets);
+            }
+        }
+    }
+
+    #[test]
+    fn test_axial() {
+        for y in 0..4 {
+            for x in 0..4 {
+                let (q, r) = to_axial(xy(x, y));
+                assert_eq!(from_axial(q, r), (x as i32, y as i32));
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_hex_line() {
+        assert_eq!(Hex.get_line(xy(0, 0), xy(0, 0)), vec![xy(0, 0)]);
+        assert_eq!(
+            Hex.get_line(xy(0, 0), xy(3, 0)),
+            vec![xy(0, 0), xy(1, 0), xy(2, 0), xy(3, 0)]
+        );
+
+        let line = Hex.get_line(xy(0, 0), xy(2, 4));
+
+        assert_eq!(line.len(), 5);
+
+        for pair in line.windows(2) {
+            assert_eq!(Hex.get_distance(pair[0], pair[1]), 1);
+        }
+    }
+
+    #[test]
+    fn test_get_render_position() {
+        assert_eq!(Square4.get_render_position(xy(3, 1)), xy(3, 1));
+        assert_eq!(Hex.get_render_position(xy(3, 0)), xy(6, 0));
+        assert_eq!(Hex.get_render_position(xy(3, 1)), xy(7, 1));
+    }
+}
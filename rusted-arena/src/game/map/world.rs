@@ -0,0 +1,118 @@
+use super::*;
+
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub struct Location {
+    pub level: usize,
+    pub index: usize,
+}
+
+pub fn location(level: usize, index: usize) -> Location {
+    Location { level, index }
+}
+
+pub struct World {
+    levels: Vec<TileMap>,
+    connections: HashMap<Location, Location>,
+}
+
+impl World {
+    pub fn new(levels: Vec<TileMap>) -> World {
+        World {
+            levels,
+            connections: HashMap::new(),
+        }
+    }
+
+    pub fn get_level(&self, level: usize) -> &TileMap {
+        self.levels
+            .get(level)
+            .unwrap_or_else(|| panic!("Level {} is unknown!", level))
+    }
+
+    pub fn get_level_mut(&mut self, level: usize) -> &mut TileMap {
+        self.levels
+            .get_mut(level)
+            .unwrap_or_else(|| panic!("Level {} is unknown!", level))
+    }
+
+    pub fn get_level_count(&self) -> usize {
+        self.levels.len()
+    }
+
+    pub fn connect(&mut self, a: Location, b: Location) {
+        self.assert_connectable(a);
+        self.assert_connectable(b);
+        self.connections.insert(a, b);
+        self.connections.insert(b, a);
+    }
+
+    pub fn get_connection(&self, from: Location) -> Option<Location> {
+        self.connections.get(&from).copied()
+    }
+
+    fn assert_connectable(&self, location: Location) {
+        let tile = self.get_level(location.level).get_tile(location.index);
+
+        assert!(
+            tile == TileType::Stairs,
+            "Location {:?} is not a stair!",
+            location
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::TileType::*;
+
+    const SIZE: Point = Point { x: 3, y: 3 };
+
+    fn create_world() -> World {
+        let upper = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 1), Stairs)
+            .build();
+        let lower = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(2, 2), Stairs)
+            .build();
+
+        World::new(vec![upper, lower])
+    }
+
+    #[test]
+    fn test_get_level() {
+        let world = create_world();
+
+        assert_eq!(world.get_level_count(), 2);
+        assert_eq!(world.get_level(0).get_tile(4), Stairs);
+        assert_eq!(world.get_level(1).get_tile(8), Stairs);
+    }
+
+    #[test]
+    #[should_panic(expected = "Level 2 is unknown!")]
+    fn test_get_unknown_level() {
+        let world = create_world();
+
+        world.get_level(2);
+    }
+
+    #[test]
+    fn test_connect() {
+        let mut world = create_world();
+
+        world.connect(location(0, 4), location(1, 8));
+
+        assert_eq!(world.get_connection(location(0, 4)), Some(location(1, 8)));
+        assert_eq!(world.get_connection(location(1, 8)), Some(location(0, 4)));
+        assert_eq!(world.get_connection(location(0, 8)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Location Location { level: 1, index: 0 } is not a stair!")]
+    fn test_connect_without_stairs() {
+        let mut world = create_world();
+
+        world.connect(location(0, 4), location(1, 0));
+    }
+}
@@ -1,4 +1,6 @@
 pub mod component;
+#[cfg(test)]
+pub mod fixture;
 pub mod map;
 pub mod rpg;
 pub mod system;
@@ -0,0 +1,7 @@
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Attribute {
+    Strength,
+    Agility,
+    Intelligence,
+    Health,
+}
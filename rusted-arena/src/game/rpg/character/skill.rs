@@ -1,10 +1,17 @@
+use crate::game::rpg::character::attribute::Attribute;
 use std::collections::HashMap;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SkillDefault {
+    Fixed(i32),
+    Attribute { attribute: Attribute, modifier: i32 },
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Skill {
     pub id: usize,
     pub name: String,
-    pub default: Option<i32>,
+    pub default: Option<SkillDefault>,
 }
 
 pub struct SkillMgr {
@@ -45,7 +52,7 @@ pub struct SkillBuilder {
 }
 
 impl SkillBuilder {
-    pub fn create(mut self, name: String, default: Option<i32>) -> SkillBuilder {
+    pub fn create(mut self, name: String, default: Option<SkillDefault>) -> SkillBuilder {
         let id = self.skills.len();
         self.skills.push(Skill { id, name, default });
         self
@@ -63,16 +70,22 @@ mod tests {
     const NAME_A: &'static str = "A";
     const NAME_B: &'static str = "B";
     const NAME_C: &'static str = "C";
+    const HEALTH_MINUS_2: SkillDefault = SkillDefault::Attribute {
+        attribute: Attribute::Health,
+        modifier: -2,
+    };
 
     #[test]
     fn test_get() {
         let manager = SkillBuilder::default()
             .create(NAME_A.to_string(), None)
-            .create(NAME_B.to_string(), Some(4))
+            .create(NAME_B.to_string(), Some(SkillDefault::Fixed(4)))
+            .create(NAME_C.to_string(), Some(HEALTH_MINUS_2))
             .build();
 
         assert_skill(&manager, 0, NAME_A, None);
-        assert_skill(&manager, 1, NAME_B, Some(4));
+        assert_skill(&manager, 1, NAME_B, Some(SkillDefault::Fixed(4)));
+        assert_skill(&manager, 2, NAME_C, Some(HEALTH_MINUS_2));
     }
 
     #[test]
@@ -95,7 +108,7 @@ mod tests {
         assert_eq!(manager.get_id(NAME_C), None);
     }
 
-    fn assert_skill(manager: &SkillMgr, id: usize, name: &str, default: Option<i32>) {
+    fn assert_skill(manager: &SkillMgr, id: usize, name: &str, default: Option<SkillDefault>) {
         let skill = manager.get(id);
         assert_eq!(skill.id, id);
         assert_eq!(skill.name, name);
@@ -0,0 +1,801 @@
+use crate::game::component::body::*;
+use crate::game::component::health::{Health, HealthState};
+use crate::game::component::speed;
+use crate::game::component::stats::Stats;
+use crate::game::component::trigger::Trigger;
+use crate::game::map::pathfinding::Path;
+use crate::game::map::{Direction, MapError, TileMap, TileType};
+use crate::game::rpg::combat::damage::Damage;
+use crate::game::system::control::get_provoked_entities;
+use crate::game::system::displacement::{move_entity, move_entity_to};
+use crate::game::system::health::HealthSystem;
+use crate::game::system::movement::*;
+use crate::game::system::scheduler::get_speed;
+use crate::game::system::trigger::TriggerSystem;
+use crate::utils::ecs::storage::ComponentStorage;
+use crate::utils::ecs::ECS;
+use std::error::Error;
+use std::fmt;
+
+pub const ATTACK_DAMAGE: i32 = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Action {
+    Move(Direction),
+    Attack(usize),
+    Wait,
+    ToggleDoor(usize),
+    PullLever(usize),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ActionError {
+    Blocked(MoveResult),
+    InvalidTarget(usize),
+    OutOfReach(usize),
+    Map(MapError),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Reaction {
+    OpportunityAttack { by: usize },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Interruption {
+    Blocked(ActionError),
+    Exhausted,
+    Triggered(Vec<Trigger>),
+    Provoked(Vec<Reaction>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Travel {
+    pub path: Path,
+    pub interruption: Option<Interruption>,
+}
+
+impl fmt::Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ActionError::Blocked(result) => write!(f, "Movement is blocked: {:?}!", result),
+            ActionError::InvalidTarget(target) => write!(f, "{} is not a valid target!", target),
+            ActionError::OutOfReach(target) => write!(f, "{} is out of reach!", target),
+            ActionError::Map(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for ActionError {}
+
+impl From<MapError> for ActionError {
+    fn from(error: MapError) -> Self {
+        ActionError::Map(error)
+    }
+}
+
+pub fn validate(
+    ecs: &ECS,
+    map: &TileMap,
+    entity: usize,
+    action: &Action,
+) -> Result<(), ActionError> {
+    let body = ecs.unwrap_component::<Body>(entity);
+
+    match *action {
+        Action::Move(dir) => {
+            let profile = get_movement_profile(ecs, entity);
+
+            match check_move(map, entity, body, profile, dir) {
+                None => Ok(()),
+                Some(result) => Err(ActionError::Blocked(result)),
+            }
+        }
+        Action::Attack(target) => validate_attack(ecs, map, entity, body, target),
+        Action::Wait => Ok(()),
+        Action::ToggleDoor(index) => {
+            validate_reach(map, body, index)?;
+
+            match map.get_tile(index) {
+                TileType::DoorOpen => validate_free(map, index),
+                TileType::DoorClosed => Ok(()),
+                found => Err(MapError::WrongTile { index, found }.into()),
+            }
+        }
+        Action::PullLever(index) => {
+            validate_reach(map, body, index)?;
+
+            match map.get_tile(index) {
+                TileType::Lever => Ok(()),
+                found => Err(MapError::WrongTile { index, found }.into()),
+            }
+        }
+    }
+}
+
+fn validate_attack(
+    ecs: &ECS,
+    map: &TileMap,
+    entity: usize,
+    body: &Body,
+    target: usize,
+) -> Result<(), ActionError> {
+    let health = ecs.get_storage_mgr().get::<Health>().get(target);
+    let stats = ecs.get_storage_mgr().get::<Stats>().get(target);
+    let target_body = ecs.get_storage_mgr().get::<Body>().get(target);
+
+    match (health, stats, target_body) {
+        (Some(health), Some(_), Some(target_body)) if target != entity => {
+            if health.state == HealthState::Dead {
+                return Err(ActionError::InvalidTarget(target));
+            }
+
+            let indices = get_indices(target_body, map.get_size());
+
+            if indices.iter().any(|i| is_in_reach(map, body, *i)) {
+                Ok(())
+            } else {
+                Err(ActionError::OutOfReach(target))
+            }
+        }
+        _ => Err(ActionError::InvalidTarget(target)),
+    }
+}
+
+fn validate_reach(map: &TileMap, body: &Body, index: usize) -> Result<(), ActionError> {
+    if !map.is_inside(index) {
+        return Err(MapError::OutOfBounds(index).into());
+    }
+
+    if is_in_reach(map, body, index) {
+        Ok(())
+    } else {
+        Err(ActionError::OutOfReach(index))
+    }
+}
+
+fn validate_free(map: &TileMap, index: usize) -> Result<(), ActionError> {
+    match map.get_entities(index).first() {
+        None => Ok(()),
+        Some((_, by)) => Err(MapError::Blocked { index, by: *by }.into()),
+    }
+}
+
+fn is_in_reach(map: &TileMap, body: &Body, index: usize) -> bool {
+    get_indices(body, map.get_size())
+        .into_iter()
+        .any(|i| i == index || map.get_neighbors(i).contains(&index))
+}
+
+pub fn get_legal_actions(ecs: &ECS, map: &TileMap, entity: usize) -> Vec<Action> {
+    let body = ecs.unwrap_component::<Body>(entity);
+    let mut candidates = vec![Action::Wait];
+    let mut reach = Vec::new();
+
+    candidates.extend(map.get_directions().iter().map(|dir| Action::Move(*dir)));
+
+    for index in get_indices(body, map.get_size()) {
+        reach.push(index);
+        reach.extend(map.get_neighbors(index));
+    }
+
+    reach.sort_unstable();
+    reach.dedup();
+
+    let mut targets: Vec<usize> = reach
+        .iter()
+        .flat_map(|index| map.get_entities(*index))
+        .map(|(_, target)| target)
+        .collect();
+
+    targets.sort_unstable();
+    targets.dedup();
+
+    candidates.extend(targets.into_iter().map(Action::Attack));
+
+    for index in reach {
+        match map.get_tile(index) {
+            TileType::DoorOpen | TileType::DoorClosed => candidates.push(Action::ToggleDoor(index)),
+            TileType::Lever => candidates.push(Action::PullLever(index)),
+            _ => {}
+        }
+    }
+
+    candidates
+        .into_iter()
+        .filter(|action| validate(ecs, map, entity, action).is_ok())
+        .collect()
+}
+
+pub fn get_movement_points(ecs: &ECS, entity: usize) -> u32 {
+    speed::get_movement_points(get_speed(ecs, entity))
+}
+
+pub fn get_reachable_tiles(ecs: &ECS, map: &TileMap, entity: usize) -> Vec<(usize, u32)> {
+    let body = ecs.unwrap_component::<Body>(entity);
+    let profile = get_movement_profile(ecs, entity);
+    let movement_points = get_movement_points(ecs, entity);
+
+    get_reachable_for_body(map, entity, body, profile, movement_points)
+}
+
+pub struct ActionSystem<'a> {
+    health: &'a HealthSystem<'a>,
+    triggers: &'a TriggerSystem<'a>,
+}
+
+impl<'a> ActionSystem<'a> {
+    pub fn new(health: &'a HealthSystem<'a>, triggers: &'a TriggerSystem<'a>) -> ActionSystem<'a> {
+        ActionSystem { health, triggers }
+    }
+
+    pub fn execute(
+        &self,
+        ecs: &mut ECS,
+        map: &mut TileMap,
+        entity: usize,
+        action: &Action,
+    ) -> Result<Vec<Reaction>, ActionError> {
+        validate(ecs, map, entity, action)?;
+
+        match *action {
+            Action::Move(dir) => {
+                let profile = get_movement_profile(ecs, entity);
+                let result = move_entity(ecs, map, entity, dir);
+                self.triggers.on_move(ecs, map, entity, profile, &result);
+                return Ok(self.provoke(ecs, map, entity, &result));
+            }
+            Action::Attack(target) => self.attack(ecs, target),
+            Action::Wait => {
+                let profile = get_movement_profile(ecs, entity);
+                let body = ecs.unwrap_component::<Body>(entity).clone();
+                self.triggers.on_stay(ecs, map, entity, profile, &body);
+            }
+            Action::ToggleDoor(index) => map.toggle_door(index)?,
+            Action::PullLever(index) => map.pull_lever(index)?,
+        }
+
+        Ok(Vec::new())
+    }
+
+    pub fn execute_path(
+        &self,
+        ecs: &mut ECS,
+        map: &mut TileMap,
+        entity: usize,
+        path: &[usize],
+    ) -> Travel {
+        let movement_points = get_movement_points(ecs, entity);
+        let profile = get_movement_profile(ecs, entity);
+        let mut travel = Travel {
+            path: Path {
+                indices: Vec::new(),
+                cost: 0,
+            },
+            interruption: None,
+        };
+
+        for &index in path {
+            let step_cost = match self.check_step(ecs, map, entity, index) {
+                Err(error) => {
+                    travel.interruption = Some(Interruption::Blocked(error));
+                    break;
+                }
+                Ok(step_cost) if travel.path.cost + step_cost > movement_points => {
+                    travel.interruption = Some(Interruption::Exhausted);
+                    break;
+                }
+                Ok(step_cost) => {
+                    let result = move_entity_to(ecs, map, entity, index);
+                    let triggered = self.triggers.on_move(ecs, map, entity, profile, &result);
+                    let reactions = self.provoke(ecs, map, entity, &result);
+
+                    if !triggered.is_empty() {
+                        travel.interruption = Some(Interruption::Triggered(triggered));
+                    } else if !reactions.is_empty() {
+                        travel.interruption = Some(Interruption::Provoked(reactions));
+                    }
+
+                    step_cost
+                }
+            };
+
+            travel.path.indices.push(index);
+            travel.path.cost += step_cost;
+
+            if travel.interruption.is_some() {
+                break;
+            }
+        }
+
+        travel
+    }
+
+    fn check_step(
+        &self,
+        ecs: &ECS,
+        map: &TileMap,
+        entity: usize,
+        index: usize,
+    ) -> Result<u32, ActionError> {
+        let body = ecs.unwrap_component::<Body>(entity);
+        let profile = get_movement_profile(ecs, entity);
+
+        if !map.get_neighbors(get_position(body)).contains(&index) {
+            return Err(ActionError::OutOfReach(index));
+        }
+
+        if let Some(result) = check_move_to(map, entity, body, profile, index) {
+            return Err(ActionError::Blocked(result));
+        }
+
+        let step_cost = get_step_cost(map, entity, body, profile, index)
+            .unwrap_or_else(|| map.get_tile(index).get_movement_cost());
+
+        Ok(step_cost)
+    }
+
+    fn provoke(
+        &self,
+        ecs: &mut ECS,
+        map: &TileMap,
+        entity: usize,
+        result: &MoveResult,
+    ) -> Vec<Reaction> {
+        if !self.health.can_take_damage(ecs, entity) {
+            return Vec::new();
+        }
+
+        get_provoked_entities(ecs, map, entity, result)
+            .into_iter()
+            .map(|by| {
+                self.attack(ecs, entity);
+                Reaction::OpportunityAttack { by }
+            })
+            .collect()
+    }
+
+    fn attack(&self, ecs: &mut ECS, target: usize) {
+        let damage = Damage {
+            rank: ATTACK_DAMAGE,
+        };
+        self.health.take_damage(ecs, target, &damage);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::component::body::Body::*;
+    use crate::game::component::faction::Faction;
+    use crate::game::component::health::HealthState::*;
+    use crate::game::component::locomotion::Locomotion;
+    use crate::game::component::occupancy::Occupancy;
+    use crate::game::component::speed::Speed;
+    use crate::game::component::trigger::TriggerEvent;
+    use crate::game::fixture::{self, add_health, create_checker, create_toughness};
+    use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::layer::Layer;
+    use crate::game::map::topology::Topology;
+    use crate::game::map::Direction::*;
+    use crate::game::map::TileType::*;
+    use crate::game::rpg::character::skill::Skill;
+    use crate::game::system::trigger::get_tile_triggers;
+    use rusted_tiles::math::point::*;
+
+    const SIZE: Point = Point { x: 3, y: 3 };
+
+    fn create_ecs(toughness: &Skill, map: &mut TileMap, bodies: Vec<Body>) -> ECS {
+        let mut ecs = fixture::create_ecs();
+
+        ecs.get_storage_mgr_mut().register::<Faction>();
+        ecs.get_storage_mgr_mut().register::<Locomotion>();
+        ecs.get_storage_mgr_mut().register::<Occupancy>();
+        ecs.get_storage_mgr_mut().register::<Speed>();
+        ecs.get_storage_mgr_mut().register::<Trigger>();
+
+        for body in bodies {
+            let entity = ecs.create_entity().with(body).get_entity();
+            add_health(&mut ecs, entity, toughness);
+        }
+
+        add_all_to_map(&mut ecs, map).unwrap();
+
+        ecs
+    }
+
+    #[test]
+    fn test_validate_move() {
+        let toughness = create_toughness();
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 1), Wall)
+            .build();
+        let ecs = create_ecs(&toughness, &mut map, vec![Simple(0), Simple(1)]);
+
+        assert_eq!(validate(&ecs, &map, 0, &Action::Move(North)), Ok(()));
+        assert_eq!(
+            validate(&ecs, &map, 0, &Action::Move(East)),
+            Err(ActionError::Blocked(MoveResult::BlockedByEntity(vec![1])))
+        );
+        assert_eq!(
+            validate(&ecs, &map, 1, &Action::Move(North)),
+            Err(ActionError::Blocked(MoveResult::BlockedByTile(4)))
+        );
+        assert_eq!(
+            validate(&ecs, &map, 0, &Action::Move(South)),
+            Err(ActionError::Blocked(MoveResult::OutOfBounds))
+        );
+    }
+
+    #[test]
+    fn test_validate_attack() {
+        let toughness = create_toughness();
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let mut ecs = create_ecs(&toughness, &mut map, vec![Simple(0), Simple(1), Simple(8)]);
+
+        assert_eq!(validate(&ecs, &map, 0, &Action::Attack(1)), Ok(()));
+        assert_eq!(
+            validate(&ecs, &map, 0, &Action::Attack(0)),
+            Err(ActionError::InvalidTarget(0))
+        );
+        assert_eq!(
+            validate(&ecs, &map, 0, &Action::Attack(2)),
+            Err(ActionError::OutOfReach(2))
+        );
+        assert_eq!(
+            validate(&ecs, &map, 0, &Action::Attack(7)),
+            Err(ActionError::InvalidTarget(7))
+        );
+
+        ecs.unwrap_component_mut::<Health>(1).state = Dead;
+
+        assert_eq!(
+            validate(&ecs, &map, 0, &Action::Attack(1)),
+            Err(ActionError::InvalidTarget(1))
+        );
+
+        let target = ecs
+            .create_entity()
+            .with(Simple(3))
+            .with(Health::default())
+            .get_entity();
+
+        assert_eq!(
+            validate(&ecs, &map, 0, &Action::Attack(target)),
+            Err(ActionError::InvalidTarget(target))
+        );
+    }
+
+    #[test]
+    fn test_validate_door_and_lever() {
+        let toughness = create_toughness();
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), DoorOpen)
+            .set_tile(xy(2, 2), Lever)
+            .build();
+        let ecs = create_ecs(&toughness, &mut map, vec![Simple(0), Simple(1)]);
+
+        assert_eq!(
+            validate(&ecs, &map, 0, &Action::ToggleDoor(1)),
+            Err(ActionError::Map(MapError::Blocked { index: 1, by: 1 }))
+        );
+        assert_eq!(
+            validate(&ecs, &map, 0, &Action::ToggleDoor(3)),
+            Err(ActionError::Map(MapError::WrongTile {
+                index: 3,
+                found: Floor
+            }))
+        );
+        assert_eq!(
+            validate(&ecs, &map, 0, &Action::PullLever(8)),
+            Err(ActionError::OutOfReach(8))
+        );
+        assert_eq!(
+            validate(&ecs, &map, 1, &Action::PullLever(8)),
+            Err(ActionError::OutOfReach(8))
+        );
+        assert_eq!(
+            validate(&ecs, &map, 0, &Action::PullLever(9)),
+            Err(ActionError::Map(MapError::OutOfBounds(9)))
+        );
+    }
+
+    #[test]
+    fn test_get_legal_actions() {
+        let toughness = create_toughness();
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(0, 1), DoorClosed)
+            .set_tile(xy(2, 1), Lever)
+            .build();
+        let ecs = create_ecs(&toughness, &mut map, vec![Simple(0), Simple(1), Simple(8)]);
+
+        assert_eq!(
+            get_legal_actions(&ecs, &map, 0),
+            vec![Action::Wait, Action::Attack(1), Action::ToggleDoor(3)]
+        );
+        assert_eq!(
+            get_legal_actions(&ecs, &map, 2),
+            vec![
+                Action::Wait,
+                Action::Move(South),
+                Action::Move(West),
+                Action::PullLever(5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_execute() {
+        let toughness = create_toughness();
+        let checker = create_checker(2);
+        let health = HealthSystem::new(&checker, &toughness);
+        let triggers = TriggerSystem::new(&health);
+        let system = ActionSystem::new(&health, &triggers);
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(0, 1), DoorClosed)
+            .build();
+        let mut ecs = create_ecs(&toughness, &mut map, vec![Simple(0), Simple(1)]);
+
+        assert_eq!(
+            system.execute(&mut ecs, &mut map, 0, &Action::Attack(1)),
+            Ok(Vec::new())
+        );
+        assert_eq!(ecs.unwrap_component::<Health>(1).state, Reeling);
+
+        assert_eq!(
+            system.execute(&mut ecs, &mut map, 0, &Action::ToggleDoor(3)),
+            Ok(Vec::new())
+        );
+        assert_eq!(map.get_tile(3), DoorOpen);
+
+        assert_eq!(
+            system.execute(&mut ecs, &mut map, 0, &Action::Move(North)),
+            Ok(vec![Reaction::OpportunityAttack { by: 1 }])
+        );
+        assert_eq!(ecs.unwrap_component::<Body>(0), &Simple(3));
+        assert_eq!(ecs.unwrap_component::<Health>(0).state, Reeling);
+        assert_eq!(map.get_entity(3, Layer::Walker), Some(&0));
+
+        assert_eq!(
+            system.execute(&mut ecs, &mut map, 0, &Action::Move(West)),
+            Err(ActionError::Blocked(MoveResult::OutOfBounds))
+        );
+        assert_eq!(
+            system.execute(&mut ecs, &mut map, 0, &Action::Wait),
+            Ok(Vec::new())
+        );
+    }
+
+    #[test]
+    fn test_execute_move_between_allies() {
+        let toughness = create_toughness();
+        let checker = create_checker(0);
+        let health = HealthSystem::new(&checker, &toughness);
+        let triggers = TriggerSystem::new(&health);
+        let system = ActionSystem::new(&health, &triggers);
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let mut ecs = create_ecs(&toughness, &mut map, vec![Simple(0), Simple(1)]);
+
+        for entity in 0..2 {
+            ecs.get_storage_mgr_mut()
+                .get_mut::<Faction>()
+                .add(entity, Faction { id: 3 });
+        }
+
+        assert_eq!(
+            system.execute(&mut ecs, &mut map, 0, &Action::Move(North)),
+            Ok(Vec::new())
+        );
+    }
+
+    #[test]
+    fn test_get_reachable_tiles() {
+        let toughness = create_toughness();
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 1), Wall)
+            .build();
+        let mut ecs = create_ecs(&toughness, &mut map, vec![Simple(0), Simple(3)]);
+
+        ecs.get_storage_mgr_mut()
+            .get_mut::<Speed>()
+            .add(0, Speed { value: 40 });
+
+        assert_eq!(get_movement_points(&ecs, 0), 2);
+        assert_eq!(get_movement_points(&ecs, 1), 5);
+        assert_eq!(
+            get_reachable_tiles(&ecs, &map, 0),
+            vec![(0, 0), (1, 1), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn test_execute_path() {
+        let toughness = create_toughness();
+        let checker = create_checker(0);
+        let health = HealthSystem::new(&checker, &toughness);
+        let triggers = TriggerSystem::new(&health);
+        let system = ActionSystem::new(&health, &triggers);
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let mut ecs = create_ecs(&toughness, &mut map, vec![Simple(0)]);
+
+        let travel = system.execute_path(&mut ecs, &mut map, 0, &[1, 2, 5, 8]);
+
+        assert_eq!(
+            travel,
+            Travel {
+                path: Path {
+                    indices: vec![1, 2, 5, 8],
+                    cost: 4,
+                },
+                interruption: None,
+            }
+        );
+        assert_eq!(ecs.unwrap_component::<Body>(0), &Simple(8));
+        assert_eq!(map.get_entity(8, Layer::Walker), Some(&0));
+    }
+
+    #[test]
+    fn test_execute_path_square8() {
+        let toughness = create_toughness();
+        let checker = create_checker(0);
+        let health = HealthSystem::new(&checker, &toughness);
+        let triggers = TriggerSystem::new(&health);
+        let system = ActionSystem::new(&health, &triggers);
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_topology(Topology::Square8)
+            .build();
+        let mut ecs = create_ecs(&toughness, &mut map, vec![Simple(0)]);
+
+        let travel = system.execute_path(&mut ecs, &mut map, 0, &[4, 8]);
+
+        assert_eq!(travel.path.indices, vec![4, 8]);
+        assert_eq!(travel.path.cost, 2);
+        assert_eq!(travel.interruption, None);
+        assert_eq!(ecs.unwrap_component::<Body>(0), &Simple(8));
+    }
+
+    #[test]
+    fn test_execute_path_hex() {
+        let toughness = create_toughness();
+        let checker = create_checker(0);
+        let health = HealthSystem::new(&checker, &toughness);
+        let triggers = TriggerSystem::new(&health);
+        let system = ActionSystem::new(&health, &triggers);
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_topology(Topology::Hex)
+            .build();
+        let mut ecs = create_ecs(&toughness, &mut map, vec![Simple(0)]);
+
+        let travel = system.execute_path(&mut ecs, &mut map, 0, &[3, 7, 6]);
+
+        assert_eq!(travel.path.indices, vec![3, 7, 6]);
+        assert_eq!(travel.interruption, None);
+        assert_eq!(ecs.unwrap_component::<Body>(0), &Simple(6));
+
+        let travel = system.execute_path(&mut ecs, &mut map, 0, &[4]);
+
+        assert_eq!(
+            travel.interruption,
+            Some(Interruption::Blocked(ActionError::OutOfReach(4)))
+        );
+        assert_eq!(ecs.unwrap_component::<Body>(0), &Simple(6));
+    }
+
+    #[test]
+    fn test_execute_path_until_exhausted() {
+        let toughness = create_toughness();
+        let checker = create_checker(0);
+        let health = HealthSystem::new(&checker, &toughness);
+        let triggers = TriggerSystem::new(&health);
+        let system = ActionSystem::new(&health, &triggers);
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let mut ecs = create_ecs(&toughness, &mut map, vec![Simple(0)]);
+
+        ecs.get_storage_mgr_mut()
+            .get_mut::<Speed>()
+            .add(0, Speed { value: 40 });
+
+        let travel = system.execute_path(&mut ecs, &mut map, 0, &[1, 2, 5]);
+
+        assert_eq!(travel.path.indices, vec![1, 2]);
+        assert_eq!(travel.path.cost, 2);
+        assert_eq!(travel.interruption, Some(Interruption::Exhausted));
+        assert_eq!(ecs.unwrap_component::<Body>(0), &Simple(2));
+    }
+
+    #[test]
+    fn test_execute_path_until_blocked() {
+        let toughness = create_toughness();
+        let checker = create_checker(0);
+        let health = HealthSystem::new(&checker, &toughness);
+        let triggers = TriggerSystem::new(&health);
+        let system = ActionSystem::new(&health, &triggers);
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 1), Wall)
+            .build();
+        let mut ecs = create_ecs(&toughness, &mut map, vec![Simple(0)]);
+
+        let travel = system.execute_path(&mut ecs, &mut map, 0, &[1, 4]);
+
+        assert_eq!(travel.path.indices, vec![1]);
+        assert_eq!(
+            travel.interruption,
+            Some(Interruption::Blocked(ActionError::Blocked(
+                MoveResult::BlockedByTile(4)
+            )))
+        );
+
+        let travel = system.execute_path(&mut ecs, &mut map, 0, &[8]);
+
+        assert_eq!(travel.path.indices, Vec::new());
+        assert_eq!(
+            travel.interruption,
+            Some(Interruption::Blocked(ActionError::OutOfReach(8)))
+        );
+        assert_eq!(ecs.unwrap_component::<Body>(0), &Simple(1));
+    }
+
+    #[test]
+    fn test_execute_path_until_triggered() {
+        let toughness = create_toughness();
+        let checker = create_checker(1);
+        let health = HealthSystem::new(&checker, &toughness);
+        let triggers = TriggerSystem::new(&health);
+        let system = ActionSystem::new(&health, &triggers);
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), Lava)
+            .build();
+        let mut ecs = create_ecs(&toughness, &mut map, vec![Simple(0)]);
+
+        let travel = system.execute_path(&mut ecs, &mut map, 0, &[1, 2]);
+        let lava: Vec<Trigger> = get_tile_triggers(Lava)
+            .into_iter()
+            .filter(|trigger| trigger.event == TriggerEvent::Enter)
+            .collect();
+
+        assert_eq!(travel.path.indices, vec![1]);
+        assert_eq!(travel.interruption, Some(Interruption::Triggered(lava)));
+        assert_eq!(ecs.unwrap_component::<Health>(0).state, Reeling);
+    }
+
+    #[test]
+    fn test_execute_path_until_provoked() {
+        let toughness = create_toughness();
+        let checker = create_checker(1);
+        let health = HealthSystem::new(&checker, &toughness);
+        let triggers = TriggerSystem::new(&health);
+        let system = ActionSystem::new(&health, &triggers);
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let mut ecs = create_ecs(&toughness, &mut map, vec![Simple(0), Simple(4)]);
+
+        let travel = system.execute_path(&mut ecs, &mut map, 0, &[1, 2, 5]);
+
+        assert_eq!(travel.path.indices, vec![1, 2]);
+        assert_eq!(
+            travel.interruption,
+            Some(Interruption::Provoked(vec![Reaction::OpportunityAttack {
+                by: 1
+            }]))
+        );
+        assert_eq!(ecs.unwrap_component::<Health>(0).state, Reeling);
+    }
+
+    #[test]
+    fn test_execute_path_without_stats_does_not_provoke() {
+        let toughness = create_toughness();
+        let checker = create_checker(0);
+        let health = HealthSystem::new(&checker, &toughness);
+        let triggers = TriggerSystem::new(&health);
+        let system = ActionSystem::new(&health, &triggers);
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let mut ecs = create_ecs(&toughness, &mut map, vec![Simple(0), Simple(4)]);
+
+        ecs.get_storage_mgr_mut().get_mut::<Stats>().remove(0);
+
+        let travel = system.execute_path(&mut ecs, &mut map, 0, &[1, 2, 5]);
+
+        assert_eq!(travel.path.indices, vec![1, 2, 5]);
+        assert_eq!(travel.interruption, None);
+        assert_eq!(ecs.unwrap_component::<Health>(0).state, Healthy);
+    }
+}
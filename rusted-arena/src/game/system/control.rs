@@ -0,0 +1,211 @@
+use crate::game::component::body::*;
+use crate::game::component::faction::{is_ally, Faction};
+use crate::game::component::health::{Health, HealthState};
+use crate::game::map::TileMap;
+use crate::game::system::movement::MoveResult;
+use crate::utils::ecs::storage::ComponentStorage;
+use crate::utils::ecs::ECS;
+
+pub fn get_zone_of_control(map: &TileMap, body: &Body) -> Vec<usize> {
+    map.get_zone_of_control(&get_indices(body, map.get_size()))
+}
+
+pub fn get_hostile_entities(ecs: &ECS, entity: usize) -> Vec<usize> {
+    let body_storage = ecs.get_storage_mgr().get::<Body>();
+    let faction_storage = ecs.get_storage_mgr().get::<Faction>();
+    let health_storage = ecs.get_storage_mgr().get::<Health>();
+    let faction = faction_storage.get(entity);
+
+    ecs.get_entities()
+        .iter()
+        .copied()
+        .filter(|other| *other != entity)
+        .filter(|other| body_storage.get(*other).is_some())
+        .filter(|other| {
+            health_storage
+                .get(*other)
+                .is_some_and(|health| health.state != HealthState::Dead)
+        })
+        .filter(|other| !is_ally(faction, faction_storage.get(*other)))
+        .collect()
+}
+
+pub fn get_controlling_entities(
+    ecs: &ECS,
+    map: &TileMap,
+    entity: usize,
+    index: usize,
+) -> Vec<usize> {
+    let body_storage = ecs.get_storage_mgr().get::<Body>();
+
+    get_hostile_entities(ecs, entity)
+        .into_iter()
+        .filter(|other| {
+            let body = body_storage.get(*other).unwrap();
+            get_zone_of_control(map, body).contains(&index)
+        })
+        .collect()
+}
+
+pub fn is_controlled(ecs: &ECS, map: &TileMap, entity: usize, index: usize) -> bool {
+    !get_controlling_entities(ecs, map, entity, index).is_empty()
+}
+
+pub fn get_provoked_entities(
+    ecs: &ECS,
+    map: &TileMap,
+    entity: usize,
+    result: &MoveResult,
+) -> Vec<usize> {
+    let left = match result {
+        MoveResult::Moved { left, .. } => left,
+        _ => return Vec::new(),
+    };
+    let body_storage = ecs.get_storage_mgr().get::<Body>();
+
+    get_hostile_entities(ecs, entity)
+        .into_iter()
+        .filter(|other| {
+            let zone = get_zone_of_control(map, body_storage.get(*other).unwrap());
+            left.iter().any(|index| zone.contains(index))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::component::body::Body::*;
+    use crate::game::component::occupancy::Occupancy;
+    use crate::game::fixture::create_ecs;
+    use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::Direction;
+    use crate::game::map::Direction::*;
+    use crate::game::map::TileType::*;
+    use crate::game::system::movement::{add_all_to_map, move_body, MovementProfile};
+    use rusted_tiles::math::point::*;
+
+    const SIZE: Point = Point { x: 5, y: 5 };
+
+    fn create(bodies: Vec<(Body, usize)>) -> (ECS, TileMap) {
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        ecs.get_storage_mgr_mut().register::<Faction>();
+        ecs.get_storage_mgr_mut().register::<Occupancy>();
+
+        for (body, faction) in bodies {
+            ecs.create_entity()
+                .with(body)
+                .with(Faction { id: faction })
+                .with(Health::default());
+        }
+
+        add_all_to_map(&mut ecs, &mut map).unwrap();
+
+        (ecs, map)
+    }
+
+    fn move_entity(ecs: &mut ECS, map: &mut TileMap, entity: usize, dir: Direction) -> MoveResult {
+        move_body(
+            map,
+            entity,
+            ecs.unwrap_component_mut::<Body>(entity),
+            MovementProfile::default(),
+            dir,
+        )
+    }
+
+    #[test]
+    fn test_get_zone_of_control() {
+        let map = TileMapBuilder::new(SIZE, Floor).build();
+
+        assert_eq!(get_zone_of_control(&map, &Simple(0)), vec![1, 5]);
+        assert_eq!(
+            get_zone_of_control(&map, &Snake(vec![0, 1, 1])),
+            vec![2, 5, 6]
+        );
+    }
+
+    #[test]
+    fn test_get_hostile_entities() {
+        let (mut ecs, _) = create(vec![
+            (Simple(0), 0),
+            (Simple(1), 0),
+            (Simple(2), 1),
+            (Simple(3), 2),
+        ]);
+
+        ecs.unwrap_component_mut::<Health>(3).state = HealthState::Dead;
+
+        assert_eq!(get_hostile_entities(&ecs, 0), vec![2]);
+        assert_eq!(get_hostile_entities(&ecs, 2), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_is_controlled() {
+        let (ecs, map) = create(vec![(Simple(0), 0), (Big(12, 2), 1)]);
+
+        assert!(is_controlled(&ecs, &map, 0, 11));
+        assert!(is_controlled(&ecs, &map, 0, 7));
+        assert!(!is_controlled(&ecs, &map, 0, 6));
+        assert!(!is_controlled(&ecs, &map, 1, 7));
+        assert_eq!(get_controlling_entities(&ecs, &map, 0, 19), vec![1]);
+    }
+
+    #[test]
+    fn test_leaving_zone_provokes() {
+        let (mut ecs, mut map) = create(vec![(Simple(6), 0), (Simple(7), 1), (Simple(1), 0)]);
+
+        let result = move_entity(&mut ecs, &mut map, 0, West);
+
+        assert_eq!(get_provoked_entities(&ecs, &map, 0, &result), vec![1]);
+    }
+
+    #[test]
+    fn test_moving_outside_zone_does_not_provoke() {
+        let (mut ecs, mut map) = create(vec![(Simple(5), 0), (Simple(7), 1)]);
+
+        let result = move_entity(&mut ecs, &mut map, 0, North);
+
+        assert!(result.is_moved());
+        assert_eq!(
+            get_provoked_entities(&ecs, &map, 0, &result),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn test_big_body_provokes() {
+        let (mut ecs, mut map) = create(vec![(Big(0, 2), 0), (Simple(2), 1), (Simple(22), 1)]);
+
+        let result = move_entity(&mut ecs, &mut map, 0, North);
+
+        assert_eq!(get_provoked_entities(&ecs, &map, 0, &result), vec![1]);
+    }
+
+    #[test]
+    fn test_snake_staying_in_zone_does_not_provoke() {
+        let (mut ecs, mut map) = create(vec![(Snake(vec![7, 6, 6]), 0), (Simple(8), 1)]);
+
+        let result = move_entity(&mut ecs, &mut map, 0, South);
+
+        assert!(result.is_moved());
+        assert_eq!(
+            get_provoked_entities(&ecs, &map, 0, &result),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn test_blocked_move_does_not_provoke() {
+        let (mut ecs, mut map) = create(vec![(Simple(6), 0), (Simple(7), 1)]);
+
+        let result = move_entity(&mut ecs, &mut map, 0, East);
+
+        assert_eq!(
+            get_provoked_entities(&ecs, &map, 0, &result),
+            Vec::<usize>::new()
+        );
+    }
+}
@@ -0,0 +1,724 @@
+use crate::game::component::body::*;
+use crate::game::component::bump::{Bump, BumpAction};
+use crate::game::component::faction::{is_ally, Faction};
+use crate::game::map::layer::Layer;
+use crate::game::map::{Direction, MapError, TileMap};
+use crate::game::rpg::combat::damage::Damage;
+use crate::game::system::health::HealthSystem;
+use crate::game::system::movement::*;
+use crate::utils::ecs::storage::ComponentStorage;
+use crate::utils::ecs::ECS;
+use rusted_tiles::math::get_point;
+
+pub const BUMP_DAMAGE: i32 = 4;
+pub const SLAM_DAMAGE: i32 = 4;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Displacement {
+    pub moved: u32,
+    pub collision: Option<MoveResult>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BumpResult {
+    Move(MoveResult),
+    Attacked(usize),
+    Swapped(usize),
+    Pushed {
+        target: usize,
+        displacement: Displacement,
+        result: MoveResult,
+    },
+}
+
+pub fn move_entity(ecs: &mut ECS, map: &mut TileMap, entity: usize, dir: Direction) -> MoveResult {
+    let profile = get_movement_profile(ecs, entity);
+    let body = ecs.unwrap_component_mut::<Body>(entity);
+    move_body(map, entity, body, profile, dir)
+}
+
+pub fn move_entity_to(ecs: &mut ECS, map: &mut TileMap, entity: usize, to: usize) -> MoveResult {
+    let profile = get_movement_profile(ecs, entity);
+    let body = ecs.unwrap_component_mut::<Body>(entity);
+    move_body_to(map, entity, body, profile, to)
+}
+
+pub fn teleport(
+    ecs: &mut ECS,
+    map: &mut TileMap,
+    entity: usize,
+    to: usize,
+    max_distance: u32,
+) -> MoveResult {
+    let profile = get_movement_profile(ecs, entity);
+    let body = ecs.unwrap_component_mut::<Body>(entity);
+
+    let target = if map.is_inside(to) {
+        find_spawn_location(map, entity, body, profile, to, max_distance).unwrap_or(to)
+    } else {
+        to
+    };
+
+    teleport_body(map, entity, body, profile, target)
+}
+
+pub fn place_entity(
+    ecs: &mut ECS,
+    map: &mut TileMap,
+    entity: usize,
+    near: usize,
+    max_distance: u32,
+) -> Result<Option<usize>, MapError> {
+    let profile = get_movement_profile(ecs, entity);
+    let body = ecs.unwrap_component_mut::<Body>(entity);
+
+    let location = match find_spawn_location(map, entity, body, profile, near, max_distance) {
+        None => return Ok(None),
+        Some(location) => location,
+    };
+
+    let mut new_body = body.clone();
+
+    set_position(&mut new_body, location);
+    add_entity_to_layer(map, &new_body, profile.layer, entity)?;
+    *body = new_body;
+
+    Ok(Some(location))
+}
+
+fn get_bump_action(ecs: &ECS, entity: usize, target: usize) -> BumpAction {
+    let faction_storage = ecs.get_storage_mgr().get::<Faction>();
+    let bump = ecs
+        .get_storage_mgr()
+        .get::<Bump>()
+        .get(entity)
+        .copied()
+        .unwrap_or_default();
+    let is_ally = is_ally(faction_storage.get(entity), faction_storage.get(target));
+
+    bump.get_action(is_ally)
+}
+
+struct Swap {
+    entity: usize,
+    layer: Layer,
+    body: Body,
+    new_body: Body,
+}
+
+pub fn swap_places(
+    ecs: &mut ECS,
+    map: &mut TileMap,
+    entity: usize,
+    other: usize,
+) -> Result<(), MapError> {
+    let first = get_swap(ecs, map, entity, other)?;
+    let second = get_swap(ecs, map, other, entity)?;
+
+    remove_entity_from_layer(map, &first.body, first.layer, entity)?;
+
+    if let Err(error) = remove_entity_from_layer(map, &second.body, second.layer, other) {
+        add_entity_to_layer(map, &first.body, first.layer, entity)?;
+        return Err(error);
+    }
+
+    if let Err(error) = add_entity_to_layer(map, &first.new_body, first.layer, entity) {
+        restore_bodies(map, &first, &second)?;
+        return Err(error);
+    }
+
+    if let Err(error) = add_entity_to_layer(map, &second.new_body, second.layer, other) {
+        remove_entity_from_layer(map, &first.new_body, first.layer, entity)?;
+        restore_bodies(map, &first, &second)?;
+        return Err(error);
+    }
+
+    *ecs.unwrap_component_mut::<Body>(entity) = first.new_body;
+    *ecs.unwrap_component_mut::<Body>(other) = second.new_body;
+
+    Ok(())
+}
+
+fn get_swap(ecs: &ECS, map: &TileMap, entity: usize, other: usize) -> Result<Swap, MapError> {
+    let map_size = map.get_size();
+    let profile = get_movement_profile(ecs, entity);
+    let body = ecs.unwrap_component::<Body>(entity).clone();
+    let to = get_position(ecs.unwrap_component::<Body>(other));
+    let from_point = get_point(get_position(&body), map_size);
+    let to_point = get_point(to, map_size);
+    let delta_x = to_point.x as i32 - from_point.x as i32;
+    let delta_y = to_point.y as i32 - from_point.y as i32;
+
+    let new_body = translate(&body, delta_x, delta_y, map_size).ok_or(MapError::OutOfBounds(to))?;
+    let indices = get_checked_indices(&new_body, map_size).ok_or(MapError::OutOfBounds(to))?;
+
+    for &index in &indices {
+        let tile = map.get_tile(index);
+
+        if !profile.mobility.can_enter(tile) {
+            return Err(MapError::WrongTile { index, found: tile });
+        }
+
+        if let Some(&by) = map
+            .get_blockers(index, profile.layer, entity)
+            .iter()
+            .find(|blocker| **blocker != other)
+        {
+            return Err(MapError::Blocked { index, by });
+        }
+    }
+
+    Ok(Swap {
+        entity,
+        layer: profile.layer,
+        body,
+        new_body,
+    })
+}
+
+fn restore_bodies(map: &mut TileMap, first: &Swap, second: &Swap) -> Result<(), MapError> {
+    for swap in [first, second].iter() {
+        add_entity_to_layer(map, &swap.body, swap.layer, swap.entity)?;
+    }
+
+    Ok(())
+}
+
+pub fn displace(
+    ecs: &mut ECS,
+    map: &mut TileMap,
+    entity: usize,
+    dir: Direction,
+    distance: u32,
+) -> Displacement {
+    force_move(ecs, map, entity, distance, |_, _| Some(dir))
+}
+
+pub fn pull(
+    ecs: &mut ECS,
+    map: &mut TileMap,
+    entity: usize,
+    towards: usize,
+    distance: u32,
+) -> Displacement {
+    force_move(ecs, map, entity, distance, |map, position| {
+        get_direction_towards(map, position, towards)
+    })
+}
+
+fn force_move<F>(
+    ecs: &mut ECS,
+    map: &mut TileMap,
+    entity: usize,
+    distance: u32,
+    get_dir: F,
+) -> Displacement
+where
+    F: Fn(&TileMap, usize) -> Option<Direction>,
+{
+    let mut moved = 0;
+
+    while moved < distance {
+        let position = get_position(ecs.unwrap_component::<Body>(entity));
+
+        let dir = match get_dir(map, position) {
+            None => break,
+            Some(dir) => dir,
+        };
+
+        let result = move_entity(ecs, map, entity, dir);
+
+        if !result.is_moved() {
+            return Displacement {
+                moved,
+                collision: Some(result),
+            };
+        }
+
+        moved += 1;
+    }
+
+    Displacement {
+        moved,
+        collision: None,
+    }
+}
+
+fn get_direction_towards(map: &TileMap, from: usize, to: usize) -> Option<Direction> {
+    let distance = map.get_distance(from, to);
+
+    if distance <= 1 {
+        return None;
+    }
+
+    map.get_directions()
+        .iter()
+        .filter_map(|dir| map.get_neighbor(from, *dir).map(|index| (*dir, index)))
+        .map(|(dir, index)| (dir, map.get_distance(index, to)))
+        .filter(|(_, new_distance)| *new_distance < distance)
+        .min_by_key(|(_, new_distance)| *new_distance)
+        .map(|(dir, _)| dir)
+}
+
+pub struct DisplacementSystem<'a> {
+    health: &'a HealthSystem<'a>,
+}
+
+impl<'a> DisplacementSystem<'a> {
+    pub fn new(health: &'a HealthSystem<'a>) -> DisplacementSystem<'a> {
+        DisplacementSystem { health }
+    }
+
+    pub fn bump(
+        &self,
+        ecs: &mut ECS,
+        map: &mut TileMap,
+        entity: usize,
+        dir: Direction,
+    ) -> BumpResult {
+        let result = move_entity(ecs, map, entity, dir);
+
+        let target = match &result {
+            MoveResult::BlockedByEntity(blockers) => blockers[0],
+            _ => return BumpResult::Move(result),
+        };
+
+        match get_bump_action(ecs, entity, target) {
+            BumpAction::Attack => {
+                self.hit(ecs, target, BUMP_DAMAGE);
+                BumpResult::Attacked(target)
+            }
+            BumpAction::Swap => match swap_places(ecs, map, entity, target) {
+                Ok(()) => BumpResult::Swapped(target),
+                Err(_) => BumpResult::Move(result),
+            },
+            BumpAction::Push => {
+                let displacement = displace(ecs, map, target, dir, 1);
+                let result = if displacement.moved > 0 {
+                    move_entity(ecs, map, entity, dir)
+                } else {
+                    result
+                };
+
+                BumpResult::Pushed {
+                    target,
+                    displacement,
+                    result,
+                }
+            }
+            BumpAction::Block => BumpResult::Move(result),
+        }
+    }
+
+    pub fn knockback(
+        &self,
+        ecs: &mut ECS,
+        map: &mut TileMap,
+        entity: usize,
+        dir: Direction,
+        distance: u32,
+    ) -> Displacement {
+        let displacement = displace(ecs, map, entity, dir, distance);
+
+        if let Some(MoveResult::BlockedByTile(_) | MoveResult::OutOfBounds) = displacement.collision
+        {
+            self.hit(ecs, entity, SLAM_DAMAGE);
+        }
+
+        displacement
+    }
+
+    fn hit(&self, ecs: &mut ECS, entity: usize, rank: i32) {
+        if !self.health.can_take_damage(ecs, entity) {
+            return;
+        }
+
+        let damage = Damage { rank };
+        self.health.take_damage(ecs, entity, &damage);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::component::body::Body::*;
+    use crate::game::component::health::Health;
+    use crate::game::component::health::HealthState::*;
+    use crate::game::component::locomotion::Locomotion;
+    use crate::game::component::occupancy::Occupancy;
+    use crate::game::fixture::{self, add_health, create_checker, create_toughness};
+    use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::Direction::*;
+    use crate::game::map::TileType::*;
+    use rusted_tiles::math::point::*;
+
+    const SIZE: Point = Point { x: 5, y: 1 };
+
+    fn create_ecs() -> ECS {
+        let mut ecs = fixture::create_ecs();
+
+        ecs.get_storage_mgr_mut().register::<Bump>();
+        ecs.get_storage_mgr_mut().register::<Faction>();
+        ecs.get_storage_mgr_mut().register::<Locomotion>();
+        ecs.get_storage_mgr_mut().register::<Occupancy>();
+
+        ecs
+    }
+
+    fn add(ecs: &mut ECS, map: &mut TileMap, body: Body, faction: usize) -> usize {
+        add_entity_to_map(map, &body, ecs.get_entities().len()).unwrap();
+        ecs.create_entity()
+            .with(body)
+            .with(Faction { id: faction })
+            .get_entity()
+    }
+
+    fn bump(ecs: &mut ECS, map: &mut TileMap, entity: usize, dir: Direction) -> BumpResult {
+        let toughness = create_toughness();
+        let checker = create_checker(0);
+        let health = HealthSystem::new(&checker, &toughness);
+        DisplacementSystem::new(&health).bump(ecs, map, entity, dir)
+    }
+
+    fn assert_position(ecs: &ECS, entity: usize, index: usize) {
+        assert_eq!(get_position(ecs.unwrap_component::<Body>(entity)), index);
+    }
+
+    #[test]
+    fn test_bump_free() {
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let entity = add(&mut ecs, &mut map, Simple(0), 0);
+
+        assert!(matches!(
+            bump(&mut ecs, &mut map, entity, East),
+            BumpResult::Move(MoveResult::Moved { .. })
+        ));
+        assert_position(&ecs, entity, 1);
+        assert_eq!(map.get_entity(1, Layer::Walker), Some(&entity));
+    }
+
+    #[test]
+    fn test_bump_enemy() {
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let entity = add(&mut ecs, &mut map, Simple(0), 0);
+        let enemy = add(&mut ecs, &mut map, Simple(1), 1);
+
+        assert_eq!(
+            bump(&mut ecs, &mut map, entity, East),
+            BumpResult::Attacked(enemy)
+        );
+        assert_position(&ecs, entity, 0);
+    }
+
+    #[test]
+    fn test_bump_attack_deals_damage() {
+        let toughness = create_toughness();
+        let checker = create_checker(1);
+        let health = HealthSystem::new(&checker, &toughness);
+        let system = DisplacementSystem::new(&health);
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let entity = add(&mut ecs, &mut map, Simple(0), 0);
+        let enemy = add(&mut ecs, &mut map, Simple(1), 1);
+
+        add_health(&mut ecs, enemy, &toughness);
+
+        assert_eq!(
+            system.bump(&mut ecs, &mut map, entity, East),
+            BumpResult::Attacked(enemy)
+        );
+        assert_eq!(ecs.unwrap_component::<Health>(enemy).state, Reeling);
+    }
+
+    #[test]
+    fn test_bump_ally() {
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let entity = add(&mut ecs, &mut map, Simple(0), 0);
+        let ally = add(&mut ecs, &mut map, Simple(1), 0);
+
+        assert_eq!(
+            bump(&mut ecs, &mut map, entity, East),
+            BumpResult::Swapped(ally)
+        );
+        assert_position(&ecs, entity, 1);
+        assert_position(&ecs, ally, 0);
+        assert_eq!(map.get_entity(0, Layer::Walker), Some(&ally));
+        assert_eq!(map.get_entity(1, Layer::Walker), Some(&entity));
+    }
+
+    #[test]
+    fn test_bump_push() {
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(3, 0), Wall)
+            .build();
+        let entity = add(&mut ecs, &mut map, Simple(0), 0);
+        let enemy = add(&mut ecs, &mut map, Simple(1), 1);
+
+        ecs.get_storage_mgr_mut().get_mut::<Bump>().add(
+            entity,
+            Bump {
+                ally: BumpAction::Swap,
+                enemy: BumpAction::Push,
+            },
+        );
+
+        assert!(matches!(
+            bump(&mut ecs, &mut map, entity, East),
+            BumpResult::Pushed {
+                displacement: Displacement {
+                    moved: 1,
+                    collision: None,
+                },
+                result: MoveResult::Moved { .. },
+                ..
+            }
+        ));
+        assert_position(&ecs, entity, 1);
+        assert_position(&ecs, enemy, 2);
+
+        assert_eq!(
+            bump(&mut ecs, &mut map, entity, East),
+            BumpResult::Pushed {
+                target: enemy,
+                displacement: Displacement {
+                    moved: 0,
+                    collision: Some(MoveResult::BlockedByTile(3)),
+                },
+                result: MoveResult::BlockedByEntity(vec![enemy]),
+            }
+        );
+        assert_position(&ecs, entity, 1);
+    }
+
+    #[test]
+    fn test_teleport() {
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let entity = add(&mut ecs, &mut map, Simple(0), 0);
+        let other = add(&mut ecs, &mut map, Simple(3), 0);
+
+        assert!(teleport(&mut ecs, &mut map, entity, 4, 0).is_moved());
+        assert_position(&ecs, entity, 4);
+
+        assert!(teleport(&mut ecs, &mut map, entity, 3, 1).is_moved());
+        assert_position(&ecs, entity, 2);
+        assert_eq!(map.get_entity(2, Layer::Walker), Some(&entity));
+        assert_eq!(map.get_entity(3, Layer::Walker), Some(&other));
+        assert_eq!(map.get_entity(4, Layer::Walker), None);
+
+        assert_eq!(
+            teleport(&mut ecs, &mut map, entity, 3, 0),
+            MoveResult::BlockedByEntity(vec![other])
+        );
+        assert_eq!(
+            teleport(&mut ecs, &mut map, entity, 5, 2),
+            MoveResult::OutOfBounds
+        );
+        assert_position(&ecs, entity, 2);
+    }
+
+    #[test]
+    fn test_place_entity() {
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(xy(3, 3), Floor)
+            .set_tile(xy(0, 0), Wall)
+            .build();
+        let entity = ecs.create_entity().with(Big(0, 2)).get_entity();
+
+        assert_eq!(place_entity(&mut ecs, &mut map, entity, 0, 0), Ok(None));
+        assert_eq!(place_entity(&mut ecs, &mut map, entity, 0, 2), Ok(Some(1)));
+        assert_position(&ecs, entity, 1);
+
+        for &i in &[1, 2, 4, 5] {
+            assert_eq!(map.get_entity(i, Layer::Walker), Some(&entity));
+        }
+    }
+
+    #[test]
+    fn test_swap_places_fails() {
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(xy(3, 3), Floor).build();
+        let entity = add(&mut ecs, &mut map, Simple(6), 0);
+        let big = add(&mut ecs, &mut map, Big(1, 2), 0);
+
+        assert_eq!(
+            swap_places(&mut ecs, &mut map, entity, big),
+            Err(MapError::OutOfBounds(6))
+        );
+        assert_position(&ecs, entity, 6);
+        assert_position(&ecs, big, 1);
+        assert_eq!(map.get_entity(6, Layer::Walker), Some(&entity));
+        assert_eq!(map.get_entity(5, Layer::Walker), Some(&big));
+    }
+
+    #[test]
+    fn test_swap_places_keeps_shape() {
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(xy(4, 3), Floor).build();
+        let entity = add(&mut ecs, &mut map, Simple(2), 0);
+        let snake = add(&mut ecs, &mut map, Snake(vec![5, 6, 10]), 0);
+
+        assert_eq!(swap_places(&mut ecs, &mut map, entity, snake), Ok(()));
+        assert_position(&ecs, entity, 5);
+        assert_eq!(ecs.unwrap_component::<Body>(snake), &Snake(vec![2, 3, 7]));
+
+        for &i in &[2, 3, 7] {
+            assert_eq!(map.get_entity(i, Layer::Walker), Some(&snake));
+        }
+
+        assert_eq!(map.get_entity(5, Layer::Walker), Some(&entity));
+        assert_eq!(map.get_entity(6, Layer::Walker), None);
+        assert_eq!(map.get_entity(10, Layer::Walker), None);
+    }
+
+    #[test]
+    fn test_swap_places_rolls_back() {
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(xy(4, 3), Floor).build();
+        let entity = add(&mut ecs, &mut map, Simple(2), 0);
+        let snake = add(&mut ecs, &mut map, Snake(vec![5, 4, 8]), 0);
+
+        assert_eq!(
+            swap_places(&mut ecs, &mut map, entity, snake),
+            Err(MapError::Blocked {
+                index: 5,
+                by: entity,
+            })
+        );
+        assert_position(&ecs, entity, 2);
+        assert_eq!(ecs.unwrap_component::<Body>(snake), &Snake(vec![5, 4, 8]));
+        assert_eq!(map.get_entity(2, Layer::Walker), Some(&entity));
+
+        for &i in &[4, 5, 8] {
+            assert_eq!(map.get_entity(i, Layer::Walker), Some(&snake));
+        }
+    }
+
+    #[test]
+    fn test_swap_places_into_chasm() {
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), Chasm)
+            .build();
+        let entity = add(&mut ecs, &mut map, Simple(0), 0);
+        let other = add(&mut ecs, &mut map, Simple(1), 0);
+
+        assert_eq!(
+            swap_places(&mut ecs, &mut map, entity, other),
+            Err(MapError::WrongTile {
+                index: 1,
+                found: Chasm,
+            })
+        );
+        assert_position(&ecs, entity, 0);
+        assert_position(&ecs, other, 1);
+        assert_eq!(map.get_entity(0, Layer::Walker), Some(&entity));
+        assert_eq!(map.get_entity(1, Layer::Walker), Some(&other));
+    }
+
+    #[test]
+    fn test_displace_big() {
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(xy(5, 3), Floor).build();
+        let big = add(&mut ecs, &mut map, Big(0, 2), 0);
+        let other = add(&mut ecs, &mut map, Simple(8), 1);
+
+        assert_eq!(
+            displace(&mut ecs, &mut map, big, East, 3),
+            Displacement {
+                moved: 1,
+                collision: Some(MoveResult::BlockedByEntity(vec![other])),
+            }
+        );
+        assert_position(&ecs, big, 1);
+    }
+
+    #[test]
+    fn test_pull() {
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let puller = add(&mut ecs, &mut map, Simple(0), 0);
+        let target = add(&mut ecs, &mut map, Simple(4), 1);
+
+        assert_eq!(
+            pull(&mut ecs, &mut map, target, 0, 5),
+            Displacement {
+                moved: 3,
+                collision: None,
+            }
+        );
+        assert_position(&ecs, target, 1);
+        assert_position(&ecs, puller, 0);
+    }
+
+    #[test]
+    fn test_knockback_into_wall() {
+        let toughness = create_toughness();
+        let checker = create_checker(1);
+        let health = HealthSystem::new(&checker, &toughness);
+        let system = DisplacementSystem::new(&health);
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(3, 0), Wall)
+            .build();
+        let entity = add(&mut ecs, &mut map, Simple(1), 0);
+
+        add_health(&mut ecs, entity, &toughness);
+
+        assert_eq!(
+            system.knockback(&mut ecs, &mut map, entity, East, 3),
+            Displacement {
+                moved: 1,
+                collision: Some(MoveResult::BlockedByTile(3)),
+            }
+        );
+        assert_position(&ecs, entity, 2);
+        assert_eq!(ecs.unwrap_component::<Health>(entity).state, Reeling);
+    }
+
+    #[test]
+    fn test_knockback_into_border() {
+        let toughness = create_toughness();
+        let checker = create_checker(1);
+        let health = HealthSystem::new(&checker, &toughness);
+        let system = DisplacementSystem::new(&health);
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let entity = add(&mut ecs, &mut map, Simple(3), 0);
+
+        add_health(&mut ecs, entity, &toughness);
+
+        assert_eq!(
+            system.knockback(&mut ecs, &mut map, entity, East, 3),
+            Displacement {
+                moved: 1,
+                collision: Some(MoveResult::OutOfBounds),
+            }
+        );
+        assert_position(&ecs, entity, 4);
+        assert_eq!(ecs.unwrap_component::<Health>(entity).state, Reeling);
+    }
+
+    #[test]
+    fn test_knockback_without_collision() {
+        let toughness = create_toughness();
+        let checker = create_checker(0);
+        let health = HealthSystem::new(&checker, &toughness);
+        let system = DisplacementSystem::new(&health);
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let entity = add(&mut ecs, &mut map, Simple(0), 0);
+
+        assert_eq!(
+            system.knockback(&mut ecs, &mut map, entity, East, 2),
+            Displacement {
+                moved: 2,
+                collision: None,
+            }
+        );
+    }
+}
@@ -3,6 +3,7 @@ use crate::game::component::stats::Stats;
 use crate::game::rpg::character::skill::Skill;
 use crate::game::rpg::check::{CheckResult, Checker};
 use crate::game::rpg::combat::damage::Damage;
+use crate::utils::ecs::storage::ComponentStorage;
 use crate::utils::ecs::ECS;
 
 pub struct HealthSystem<'a> {
@@ -11,6 +12,17 @@ pub struct HealthSystem<'a> {
 }
 
 impl<'a> HealthSystem<'a> {
+    pub fn new(checker: &'a dyn Checker, toughness: &'a Skill) -> HealthSystem<'a> {
+        HealthSystem { checker, toughness }
+    }
+
+    pub fn can_take_damage(&self, ecs: &ECS, target: usize) -> bool {
+        let storage_mgr = ecs.get_storage_mgr();
+
+        storage_mgr.get::<Health>().get(target).is_some()
+            && storage_mgr.get::<Stats>().get(target).is_some()
+    }
+
     pub fn take_damage(&self, ecs: &mut ECS, target: usize, damage: &Damage) {
         let toughness_rank = self.get_toughness(ecs, target);
         let health = ecs.unwrap_component_mut::<Health>(target);
@@ -40,6 +52,8 @@ mod tests {
     use crate::game::component::health::HealthState;
     use crate::game::component::health::HealthState::*;
     use crate::game::component::stats::StatsBuilder;
+    use crate::game::fixture::create_toughness;
+    use crate::game::rpg::character::attribute::Attribute;
     use crate::game::rpg::character::skill::Skill;
     use crate::game::rpg::check::*;
 
@@ -53,6 +67,37 @@ mod tests {
         test_take_damage(CheckResult::Success(1), 6, 4, Reeling, 0)
     }
 
+    #[test]
+    fn test_take_damage_with_default_toughness() {
+        let mut mock = MockChecker::new();
+        mock.expect_check()
+            .withf(|rank, difficulty| *rank == 4 && *difficulty == 3)
+            .return_const(CheckResult::Success(1));
+
+        let toughness = create_toughness();
+
+        let mut ecs = ECS::new();
+
+        ecs.get_storage_mgr_mut().register::<Health>();
+        ecs.get_storage_mgr_mut().register::<Stats>();
+
+        let entity = ecs
+            .create_entity()
+            .with(Health::default())
+            .with(
+                StatsBuilder::default()
+                    .add_attribute(Attribute::Health, 5)
+                    .build(),
+            )
+            .get_entity();
+
+        let system = HealthSystem::new(&mock, &toughness);
+
+        system.take_damage(&mut ecs, entity, &Damage { rank: 4 });
+
+        assert_eq!(ecs.unwrap_component::<Health>(entity).state, Reeling);
+    }
+
     fn test_take_damage(
         check_result: CheckResult,
         toughness_rank: i32,
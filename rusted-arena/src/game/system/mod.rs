@@ -1,3 +1,10 @@
+pub mod action;
+pub mod control;
+pub mod displacement;
 pub mod health;
 pub mod movement;
 pub mod rendering;
+pub mod scheduler;
+pub mod snake;
+pub mod trigger;
+pub mod vision;
@@ -1,81 +1,525 @@
-use crate::game::component::body::{update_position, Body};
-use crate::game::map::{Direction, TileMap};
+use crate::game::component::body::*;
+use crate::game::component::level::Level;
+use crate::game::component::locomotion::Locomotion;
+use crate::game::component::occupancy::Occupancy;
+use crate::game::map::connectivity::is_reachable;
+use crate::game::map::layer::Layer;
+use crate::game::map::mobility::Mobility;
+use crate::game::map::pathfinding::{find_path, Path};
+use crate::game::map::world::{location, World};
+use crate::game::map::{Direction, MapError, TileMap, MIN_MOVEMENT_COST};
 use crate::utils::ecs::storage::ComponentStorage;
 use crate::utils::ecs::ECS;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
 
-pub fn add_all_to_map(ecs: &mut ECS, map: &mut TileMap) {
+pub fn add_all_to_map(ecs: &mut ECS, map: &mut TileMap) -> Result<(), MapError> {
     let body_storage = ecs.get_storage_mgr().get::<Body>();
+    let occupancy_storage = ecs.get_storage_mgr().get::<Occupancy>();
 
     for &entity in ecs.get_entities() {
         if let Some(body) = body_storage.get(entity) {
-            add_entity_to_map(map, body, entity);
+            let layer = get_layer(occupancy_storage.get(entity));
+            add_entity_to_layer(map, body, layer, entity)?;
         }
     }
+
+    Ok(())
 }
 
-pub fn add_entity_to_map(map: &mut TileMap, body: &Body, entity: usize) {
-    match body {
-        Body::Simple(index) => {
-            map.add_entity(*index, entity);
-        }
-        Body::Big(index, size) => {
-            map.add_entity_to_square(*index, *size, entity);
-        }
-        Body::Snake(indices) => {
-            for index in indices {
-                map.add_entity(*index, entity);
+pub fn add_all_to_world(ecs: &mut ECS, world: &mut World) -> Result<(), MapError> {
+    let body_storage = ecs.get_storage_mgr().get::<Body>();
+    let level_storage = ecs.get_storage_mgr().get::<Level>();
+    let occupancy_storage = ecs.get_storage_mgr().get::<Occupancy>();
+
+    for &entity in ecs.get_entities() {
+        if let Some(body) = body_storage.get(entity) {
+            if let Some(level) = level_storage.get(entity) {
+                let layer = get_layer(occupancy_storage.get(entity));
+                add_entity_to_layer(world.get_level_mut(level.id), body, layer, entity)?;
             }
         }
     }
+
+    Ok(())
+}
+
+pub fn get_entity_layer(ecs: &ECS, entity: usize) -> Layer {
+    get_layer(ecs.get_storage_mgr().get::<Occupancy>().get(entity))
+}
+
+pub fn get_movement_profile(ecs: &ECS, entity: usize) -> MovementProfile {
+    let locomotion_storage = ecs.get_storage_mgr().get::<Locomotion>();
+    let mobility = locomotion_storage
+        .get(entity)
+        .map_or_else(Mobility::default, |l| l.mobility);
+
+    MovementProfile::new(get_entity_layer(ecs, entity), mobility)
+}
+
+fn get_layer(occupancy: Option<&Occupancy>) -> Layer {
+    occupancy.map_or(Layer::Walker, |o| o.layer)
 }
 
-fn update_entity_on_map(map: &mut TileMap, body: &Body, index: usize, entity: usize) {
+pub fn add_entity_to_map(map: &mut TileMap, body: &Body, entity: usize) -> Result<(), MapError> {
+    add_entity_to_layer(map, body, Layer::Walker, entity)
+}
+
+pub fn add_entity_to_layer(
+    map: &mut TileMap,
+    body: &Body,
+    layer: Layer,
+    entity: usize,
+) -> Result<(), MapError> {
     match body {
-        Body::Simple(old_index) => {
-            map.remove_entity(*old_index, entity);
-            map.add_entity(index, entity);
-        }
-        Body::Big(old_index, size) => {
-            map.remove_entity_from_square(*old_index, *size, entity);
-            map.add_entity_to_square(index, *size, entity);
+        Body::Simple(index) => map.add_entity(*index, layer, entity),
+        Body::Big(index, size) => map.add_entity_to_square(*index, *size, layer, entity),
+        Body::Snake(indices) => map.add_entity_to_indices(indices, layer, entity),
+        Body::Shaped(index, _) => {
+            let indices =
+                get_checked_indices(body, map.get_size()).ok_or(MapError::OutOfBounds(*index))?;
+            map.add_entity_to_indices(&indices, layer, entity)
         }
-        Body::Snake(indices) => {
-            let length = indices.len();
-            let last_index = indices[length - 1];
-            let count = indices.iter().filter(|&i| *i == last_index).count();
+    }
+}
 
-            if count == 1 {
-                map.remove_entity(last_index, entity);
-            }
+pub fn remove_entity_from_map(
+    map: &mut TileMap,
+    body: &Body,
+    entity: usize,
+) -> Result<(), MapError> {
+    remove_entity_from_layer(map, body, Layer::Walker, entity)
+}
 
-            map.add_entity(index, entity);
+pub fn remove_entity_from_layer(
+    map: &mut TileMap,
+    body: &Body,
+    layer: Layer,
+    entity: usize,
+) -> Result<(), MapError> {
+    match body {
+        Body::Simple(index) => map.remove_entity(*index, layer, entity),
+        Body::Big(index, size) => map.remove_entity_from_square(*index, *size, layer, entity),
+        Body::Snake(_) | Body::Shaped(_, _) => {
+            let indices = get_indices(body, map.get_size());
+            map.remove_entity_from_indices(&indices, layer, entity)
         }
     }
 }
 
-pub fn move_body(map: &mut TileMap, entity: usize, body: &mut Body, dir: Direction) -> bool {
-    match get_new_position(map, entity, body, dir) {
-        None => false,
-        Some(index) => {
-            update_entity_on_map(map, body, index, entity);
-            update_position(body, index);
-            true
-        }
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct MovementProfile {
+    pub layer: Layer,
+    pub mobility: Mobility,
+}
+
+impl MovementProfile {
+    pub fn new(layer: Layer, mobility: Mobility) -> MovementProfile {
+        MovementProfile { layer, mobility }
+    }
+}
+
+impl Default for MovementProfile {
+    fn default() -> Self {
+        MovementProfile::new(Layer::Walker, Mobility::default())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MoveResult {
+    Moved {
+        from: usize,
+        to: usize,
+        entered: Vec<usize>,
+        left: Vec<usize>,
+        cost: u32,
+    },
+    BlockedByTile(usize),
+    BlockedByEntity(Vec<usize>),
+    OutOfBounds,
+    NotAdjacent(usize),
+    Failed(MapError),
+}
+
+impl MoveResult {
+    pub fn is_moved(&self) -> bool {
+        matches!(self, MoveResult::Moved { .. })
+    }
+}
+
+pub fn move_body(
+    map: &mut TileMap,
+    entity: usize,
+    body: &mut Body,
+    profile: MovementProfile,
+    dir: Direction,
+) -> MoveResult {
+    let new_body = match get_moved_body(map, body, dir) {
+        None => return MoveResult::OutOfBounds,
+        Some(new_body) => new_body,
+    };
+
+    relocate_body(map, entity, body, new_body, profile)
+}
+
+pub fn check_move(
+    map: &TileMap,
+    entity: usize,
+    body: &Body,
+    profile: MovementProfile,
+    dir: Direction,
+) -> Option<MoveResult> {
+    let new_indices = get_moved_body(map, body, dir)
+        .and_then(|new_body| get_checked_indices(&new_body, map.get_size()));
+
+    match new_indices {
+        None => Some(MoveResult::OutOfBounds),
+        Some(indices) => check_indices(map, &indices, profile, entity),
     }
 }
 
-fn get_new_position(map: &TileMap, entity: usize, body: &Body, dir: Direction) -> Option<usize> {
+pub fn move_body_to(
+    map: &mut TileMap,
+    entity: usize,
+    body: &mut Body,
+    profile: MovementProfile,
+    to: usize,
+) -> MoveResult {
+    let new_body = match get_stepped_body(map, body, to) {
+        None => return MoveResult::NotAdjacent(to),
+        Some(new_body) => new_body,
+    };
+
+    relocate_body(map, entity, body, new_body, profile)
+}
+
+pub fn check_move_to(
+    map: &TileMap,
+    entity: usize,
+    body: &Body,
+    profile: MovementProfile,
+    to: usize,
+) -> Option<MoveResult> {
+    let new_indices = match get_stepped_body(map, body, to) {
+        None => return Some(MoveResult::NotAdjacent(to)),
+        Some(new_body) => get_checked_indices(&new_body, map.get_size()),
+    };
+
+    match new_indices {
+        None => Some(MoveResult::OutOfBounds),
+        Some(indices) => check_indices(map, &indices, profile, entity),
+    }
+}
+
+fn get_stepped_body(map: &TileMap, body: &Body, to: usize) -> Option<Body> {
+    let from = get_position(body);
+    let dir = map
+        .get_directions()
+        .iter()
+        .copied()
+        .find(|dir| map.get_neighbor(from, *dir) == Some(to))?;
+
+    get_moved_body(map, body, dir)
+}
+
+fn get_moved_body(map: &TileMap, body: &Body, dir: Direction) -> Option<Body> {
+    let to = map.get_neighbor(get_position(body), dir)?;
+    let mut new_body = body.clone();
+
+    update_position(&mut new_body, to);
+    turn(&mut new_body, dir);
+
+    Some(new_body)
+}
+
+pub fn teleport_body(
+    map: &mut TileMap,
+    entity: usize,
+    body: &mut Body,
+    profile: MovementProfile,
+    to: usize,
+) -> MoveResult {
+    if !map.is_inside(to) {
+        return MoveResult::OutOfBounds;
+    }
+
+    let mut new_body = body.clone();
+
+    set_position(&mut new_body, to);
+
+    relocate_body(map, entity, body, new_body, profile)
+}
+
+fn relocate_body(
+    map: &mut TileMap,
+    entity: usize,
+    body: &mut Body,
+    new_body: Body,
+    profile: MovementProfile,
+) -> MoveResult {
+    let layer = profile.layer;
+    let map_size = map.get_size();
+    let old_indices = get_indices(body, map_size);
+
+    let new_indices = match get_checked_indices(&new_body, map_size) {
+        None => return MoveResult::OutOfBounds,
+        Some(indices) => indices,
+    };
+
+    if let Some(blocked) = check_indices(map, &new_indices, profile, entity) {
+        return blocked;
+    }
+
+    let entered = get_difference(&new_indices, &old_indices);
+    let left = get_difference(&old_indices, &new_indices);
+
+    if let Err(error) = map.add_entity_to_indices(&new_indices, layer, entity) {
+        return MoveResult::Failed(error);
+    }
+
+    if let Err(error) = map.remove_entity_from_indices(&left, layer, entity) {
+        let _ = map.remove_entity_from_indices(&entered, layer, entity);
+        return MoveResult::Failed(error);
+    }
+
+    let from = get_position(body);
+    let to = get_position(&new_body);
+
+    *body = new_body;
+
+    MoveResult::Moved {
+        from,
+        to,
+        entered,
+        left,
+        cost: get_body_cost(map, body),
+    }
+}
+
+fn get_body_cost(map: &TileMap, body: &Body) -> u32 {
     match body {
-        Body::Simple(index) => map
-            .get_neighbor(*index, dir)
-            .filter(|i| map.is_free(*i, entity)),
-        Body::Big(index, size) => map
-            .get_neighbor(*index, dir)
-            .filter(|i| map.is_square_free(*i, *size, entity)),
-        Body::Snake(ref indices) => map
-            .get_neighbor(indices[0], dir)
-            .filter(|i| map.is_free(*i, entity)),
+        Body::Snake(_) => map.get_tile(get_position(body)).get_movement_cost(),
+        _ => get_indices_cost(map, &get_indices(body, map.get_size())),
+    }
+}
+
+fn get_indices_cost(map: &TileMap, indices: &[usize]) -> u32 {
+    indices
+        .iter()
+        .map(|i| map.get_tile(*i).get_movement_cost())
+        .max()
+        .unwrap_or(MIN_MOVEMENT_COST)
+}
+
+fn check_indices(
+    map: &TileMap,
+    indices: &[usize],
+    profile: MovementProfile,
+    entity: usize,
+) -> Option<MoveResult> {
+    if let Some(index) = indices
+        .iter()
+        .find(|i| !profile.mobility.can_enter(map.get_tile(**i)))
+    {
+        return Some(MoveResult::BlockedByTile(*index));
+    }
+
+    let mut blockers: Vec<usize> = indices
+        .iter()
+        .flat_map(|i| map.get_blockers(*i, profile.layer, entity))
+        .collect();
+
+    if blockers.is_empty() {
+        return None;
+    }
+
+    blockers.sort_unstable();
+    blockers.dedup();
+
+    Some(MoveResult::BlockedByEntity(blockers))
+}
+
+fn get_difference(indices: &[usize], other: &[usize]) -> Vec<usize> {
+    indices
+        .iter()
+        .filter(|i| !other.contains(i))
+        .copied()
+        .collect()
+}
+
+pub fn change_level(
+    world: &mut World,
+    entity: usize,
+    body: &mut Body,
+    profile: MovementProfile,
+    level: &mut Level,
+) -> Result<(), MapError> {
+    let layer = profile.layer;
+    let from = location(level.id, get_position(body));
+
+    let to = match world.get_connection(from) {
+        None => {
+            let found = world.get_level(from.level).get_tile(from.index);
+            return Err(MapError::WrongTile {
+                index: from.index,
+                found,
+            });
+        }
+        Some(to) => to,
+    };
+
+    let mut new_body = body.clone();
+    set_position(&mut new_body, to.index);
+
+    let target = world.get_level(to.level);
+    let indices =
+        get_checked_indices(&new_body, target.get_size()).ok_or(MapError::OutOfBounds(to.index))?;
+
+    for &index in &indices {
+        let found = target.get_tile(index);
+
+        if !profile.mobility.can_enter(found) {
+            return Err(MapError::WrongTile { index, found });
+        }
+    }
+
+    add_entity_to_layer(world.get_level_mut(to.level), &new_body, layer, entity)?;
+
+    if let Err(error) =
+        remove_entity_from_layer(world.get_level_mut(from.level), body, layer, entity)
+    {
+        remove_entity_from_layer(world.get_level_mut(to.level), &new_body, layer, entity)?;
+        return Err(error);
+    }
+
+    *body = new_body;
+    level.id = to.level;
+
+    Ok(())
+}
+
+pub fn can_occupy(
+    map: &TileMap,
+    entity: usize,
+    body: &Body,
+    profile: MovementProfile,
+    index: usize,
+) -> bool {
+    get_placed_indices(map, body, index).is_some_and(|indices| {
+        indices.iter().all(|i| {
+            map.is_free_with_mobility(*i, profile.layer, profile.mobility, entity) == Ok(true)
+        })
+    })
+}
+
+pub fn find_spawn_location(
+    map: &TileMap,
+    entity: usize,
+    body: &Body,
+    profile: MovementProfile,
+    near: usize,
+    max_distance: u32,
+) -> Option<usize> {
+    let size = map.get_size();
+    let mut candidates: Vec<usize> = (0..(size.x * size.y) as usize)
+        .filter(|i| map.get_distance(near, *i) <= max_distance)
+        .collect();
+
+    candidates.sort_by_key(|i| (map.get_distance(near, *i), *i));
+    candidates
+        .into_iter()
+        .find(|i| can_occupy(map, entity, body, profile, *i))
+}
+
+fn get_placed_body(body: &Body, index: usize) -> Body {
+    let mut placed = body.clone();
+    set_position(&mut placed, index);
+    placed
+}
+
+fn get_placed_indices(map: &TileMap, body: &Body, index: usize) -> Option<Vec<usize>> {
+    get_checked_indices(&get_placed_body(body, index), map.get_size())
+}
+
+pub fn find_path_for_body(
+    map: &TileMap,
+    entity: usize,
+    body: &Body,
+    profile: MovementProfile,
+    goal: usize,
+    max_nodes: usize,
+) -> Option<Path> {
+    let start = get_position(body);
+
+    find_path(map, start, goal, max_nodes, MIN_MOVEMENT_COST, |index| {
+        get_step_cost(map, entity, body, profile, index)
+    })
+}
+
+pub fn get_step_cost(
+    map: &TileMap,
+    entity: usize,
+    body: &Body,
+    profile: MovementProfile,
+    index: usize,
+) -> Option<u32> {
+    if !can_occupy(map, entity, body, profile, index) {
+        return None;
+    }
+
+    Some(get_body_cost(map, &get_placed_body(body, index)))
+}
+
+pub fn get_reachable_for_body(
+    map: &TileMap,
+    entity: usize,
+    body: &Body,
+    profile: MovementProfile,
+    movement_points: u32,
+) -> Vec<(usize, u32)> {
+    let start = get_position(body);
+    let mut costs: HashMap<usize, u32> = HashMap::new();
+    let mut open = BinaryHeap::new();
+
+    costs.insert(start, 0);
+    open.push(Reverse((0, start)));
+
+    while let Some(Reverse((cost, index))) = open.pop() {
+        if cost > costs[&index] {
+            continue;
+        }
+
+        for neighbor in map.get_neighbors(index) {
+            let step_cost = match get_step_cost(map, entity, body, profile, neighbor) {
+                None => continue,
+                Some(step_cost) => step_cost,
+            };
+
+            let new_cost = cost + step_cost;
+
+            if new_cost <= movement_points && costs.get(&neighbor).is_none_or(|old| new_cost < *old)
+            {
+                costs.insert(neighbor, new_cost);
+                open.push(Reverse((new_cost, neighbor)));
+            }
+        }
     }
+
+    let mut reachable: Vec<(usize, u32)> = costs.into_iter().collect();
+    reachable.sort_unstable();
+    reachable
+}
+
+pub fn is_reachable_for_body(
+    map: &TileMap,
+    entity: usize,
+    body: &Body,
+    profile: MovementProfile,
+    goal: usize,
+) -> bool {
+    is_reachable(map, get_position(body), goal, |index| {
+        can_occupy(map, entity, body, profile, index)
+    })
 }
 
 #[cfg(test)]
@@ -83,11 +527,16 @@ mod tests {
     use super::*;
     use crate::game::component::body::Body::*;
     use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::layer::BlockingRules;
+    use crate::game::map::mobility::MovementMode;
+    use crate::game::map::topology::Topology;
     use crate::game::map::Direction::*;
-    use crate::game::map::TileType::Floor;
-    use rusted_tiles::math::point::Point;
+    use crate::game::map::TileType::{Chasm, Floor, Rubble, Stairs, Wall, Water};
+    use rusted_tiles::math::get_index;
+    use rusted_tiles::math::point::*;
 
     const SIZE: Point = Point { x: 3, y: 3 };
+    const CORRIDOR_SIZE: Point = Point { x: 6, y: 5 };
     const ENTITY: usize = 42;
 
     // add_all_to_map
@@ -98,11 +547,32 @@ mod tests {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
 
         ecs.get_storage_mgr_mut().register::<Body>();
+        ecs.get_storage_mgr_mut().register::<Occupancy>();
         ecs.create_entity().with(Big(4, 2));
 
-        add_all_to_map(&mut ecs, &mut map);
+        add_all_to_map(&mut ecs, &mut map).unwrap();
+
+        assert_big_entity(&map, 0);
+    }
+
+    #[test]
+    fn test_add_all_to_map_with_layers() {
+        let mut ecs = ECS::new();
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        ecs.get_storage_mgr_mut().register::<Body>();
+        ecs.get_storage_mgr_mut().register::<Occupancy>();
+        ecs.create_entity().with(Simple(4));
+        ecs.create_entity().with(Simple(4)).with(Occupancy {
+            layer: Layer::Flyer,
+        });
+
+        add_all_to_map(&mut ecs, &mut map).unwrap();
 
-        assert_big_entity(&mut map, 0);
+        assert_eq!(
+            map.get_entities(4),
+            vec![(Layer::Walker, 0), (Layer::Flyer, 1)]
+        );
     }
 
     // add_entity_to_map
@@ -110,7 +580,7 @@ mod tests {
     #[test]
     fn test_add_entity_to_map_simple() {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
-        add_entity_to_map(&mut map, &Simple(0), ENTITY);
+        add_entity_to_map(&mut map, &Simple(0), ENTITY).unwrap();
 
         assert_simple(&mut map)
     }
@@ -118,7 +588,7 @@ mod tests {
     #[test]
     fn test_add_entity_to_map_big() {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
-        add_entity_to_map(&mut map, &Big(4, 2), ENTITY);
+        add_entity_to_map(&mut map, &Big(4, 2), ENTITY).unwrap();
 
         assert_big(&mut map);
     }
@@ -126,11 +596,24 @@ mod tests {
     #[test]
     fn test_add_entity_to_map_sanke() {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
-        add_entity_to_map(&mut map, &Snake(vec![1, 4, 7]), ENTITY);
+        add_entity_to_map(&mut map, &Snake(vec![1, 4, 7]), ENTITY).unwrap();
 
         assert_snake(&mut map);
     }
 
+    #[test]
+    fn test_add_entity_to_map_blocked() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        map.add_entity(7, Layer::Walker, 99).unwrap();
+
+        assert_eq!(
+            add_entity_to_map(&mut map, &Snake(vec![1, 4, 7]), ENTITY),
+            Err(MapError::Blocked { index: 7, by: 99 })
+        );
+        assert_eq!(map.get_entity(1, Layer::Walker), None);
+        assert_eq!(map.get_entity(4, Layer::Walker), None);
+    }
+
     // move_body
 
     #[test]
@@ -138,21 +621,73 @@ mod tests {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
         let mut body = Simple(1);
 
-        add_entity_to_map(&mut map, &body, ENTITY);
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
 
-        assert_eq!(move_body(&mut map, ENTITY, &mut body, West), true);
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                West
+            ),
+            MoveResult::Moved {
+                from: 1,
+                to: 0,
+                entered: vec![0],
+                left: vec![1],
+                cost: 1,
+            }
+        );
         assert_eq!(body, Simple(0));
         assert_simple(&mut map);
     }
 
+    #[test]
+    fn test_move_body_not_on_map() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let mut body = Big(0, 2);
+
+        map.add_entity_to_indices(&[0, 1, 4], Layer::Walker, ENTITY)
+            .unwrap();
+
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                East
+            ),
+            MoveResult::Failed(MapError::NotPresent {
+                index: 3,
+                entity: ENTITY
+            })
+        );
+        assert_eq!(body, Big(0, 2));
+        assert_eq!(map.get_entity(0, Layer::Walker), Some(&ENTITY));
+        assert_eq!(map.get_entity(2, Layer::Walker), None);
+        assert_eq!(map.get_entity(4, Layer::Walker), Some(&ENTITY));
+        assert_eq!(map.get_entity(5, Layer::Walker), None);
+    }
+
     #[test]
     fn test_move_body_simple_blocked() {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
         let mut body = Simple(0);
 
-        add_entity_to_map(&mut map, &body, ENTITY);
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
 
-        assert_eq!(move_body(&mut map, ENTITY, &mut body, West), false);
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                West
+            ),
+            MoveResult::OutOfBounds
+        );
         assert_eq!(body, Simple(0));
         assert_simple(&mut map);
     }
@@ -162,9 +697,24 @@ mod tests {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
         let mut body = Big(1, 2);
 
-        add_entity_to_map(&mut map, &body, ENTITY);
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
 
-        assert_eq!(move_body(&mut map, ENTITY, &mut body, North), true);
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                North
+            ),
+            MoveResult::Moved {
+                from: 1,
+                to: 4,
+                entered: vec![7, 8],
+                left: vec![1, 2],
+                cost: 1,
+            }
+        );
         assert_eq!(body, Big(4, 2));
         assert_big(&mut map);
     }
@@ -174,9 +724,18 @@ mod tests {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
         let mut body = Big(4, 2);
 
-        add_entity_to_map(&mut map, &body, ENTITY);
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
 
-        assert_eq!(move_body(&mut map, ENTITY, &mut body, North), false);
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                North
+            ),
+            MoveResult::OutOfBounds
+        );
         assert_eq!(body, Big(4, 2));
         assert_big(&mut map);
     }
@@ -186,31 +745,1101 @@ mod tests {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
         let mut body = Snake(vec![4, 7, 8]);
 
-        add_entity_to_map(&mut map, &body, ENTITY);
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
 
-        assert_eq!(move_body(&mut map, ENTITY, &mut body, South), true);
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                South
+            ),
+            MoveResult::Moved {
+                from: 4,
+                to: 1,
+                entered: vec![1],
+                left: vec![8],
+                cost: 1,
+            }
+        );
         assert_eq!(body, Snake(vec![1, 4, 7]));
         assert_snake(&mut map);
     }
 
+    #[test]
+    fn test_move_body_on_hex_map() {
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_topology(Topology::Hex)
+            .build();
+        let mut body = Simple(4);
+
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                North
+            ),
+            MoveResult::OutOfBounds
+        );
+        assert!(move_body(
+            &mut map,
+            ENTITY,
+            &mut body,
+            MovementProfile::default(),
+            NorthEast
+        )
+        .is_moved());
+        assert_eq!(body, Simple(8));
+        assert!(move_body(
+            &mut map,
+            ENTITY,
+            &mut body,
+            MovementProfile::default(),
+            SouthWest
+        )
+        .is_moved());
+        assert_eq!(body, Simple(4));
+        assert!(move_body(
+            &mut map,
+            ENTITY,
+            &mut body,
+            MovementProfile::default(),
+            SouthEast
+        )
+        .is_moved());
+        assert_eq!(body, Simple(2));
+    }
+
+    #[test]
+    fn test_move_body_diagonally() {
+        let mut square4 = TileMapBuilder::new(SIZE, Floor).build();
+        let mut square8 = TileMapBuilder::new(SIZE, Floor)
+            .set_topology(Topology::Square8)
+            .build();
+        let mut body = Simple(0);
+
+        add_entity_to_map(&mut square4, &body, ENTITY).unwrap();
+        add_entity_to_map(&mut square8, &body, ENTITY).unwrap();
+
+        assert_eq!(
+            move_body(
+                &mut square4,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                NorthEast
+            ),
+            MoveResult::OutOfBounds
+        );
+        assert!(move_body(
+            &mut square8,
+            ENTITY,
+            &mut body,
+            MovementProfile::default(),
+            NorthEast
+        )
+        .is_moved());
+        assert_eq!(body, Simple(4));
+    }
+
+    #[test]
+    fn test_move_body_into_water() {
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), Water)
+            .build();
+        let mut body = Simple(0);
+
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                East
+            ),
+            MoveResult::Moved {
+                from: 0,
+                to: 1,
+                entered: vec![1],
+                left: vec![0],
+                cost: 2,
+            }
+        );
+    }
+
     #[test]
     fn test_move_body_snake_blocked() {
         let mut map = TileMapBuilder::new(SIZE, Floor).build();
         let mut body = Snake(vec![1, 4, 7]);
 
-        add_entity_to_map(&mut map, &body, ENTITY);
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
 
-        assert_eq!(move_body(&mut map, ENTITY, &mut body, South), false);
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                South
+            ),
+            MoveResult::OutOfBounds
+        );
         assert_eq!(body, Snake(vec![1, 4, 7]));
         assert_snake(&mut map);
     }
 
+    #[test]
+    fn test_move_body_snake_into_own_tail() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let mut body = Snake(vec![0, 1, 4, 3]);
+
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                North
+            ),
+            MoveResult::Moved {
+                from: 0,
+                to: 3,
+                entered: vec![],
+                left: vec![],
+                cost: 1,
+            }
+        );
+        assert_eq!(body, Snake(vec![3, 0, 1, 4]));
+        assert_eq!(map.get_entity(0, Layer::Walker), Some(&ENTITY));
+        assert_eq!(map.get_entity(1, Layer::Walker), Some(&ENTITY));
+        assert_eq!(map.get_entity(3, Layer::Walker), Some(&ENTITY));
+        assert_eq!(map.get_entity(4, Layer::Walker), Some(&ENTITY));
+    }
+
+    #[test]
+    fn test_move_body_flyer_over_walker() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let mut body = Simple(0);
+
+        map.add_entity(1, Layer::Walker, 99).unwrap();
+        add_entity_to_layer(&mut map, &body, Layer::Flyer, ENTITY).unwrap();
+
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::new(Layer::Flyer, Mobility::default()),
+                East
+            ),
+            MoveResult::Moved {
+                from: 0,
+                to: 1,
+                entered: vec![1],
+                left: vec![0],
+                cost: 1,
+            }
+        );
+        assert_eq!(body, Simple(1));
+        assert_eq!(map.get_entity(0, Layer::Flyer), None);
+        assert_eq!(map.get_entity(1, Layer::Flyer), Some(&ENTITY));
+        assert_eq!(map.get_entity(1, Layer::Walker), Some(&99));
+    }
+
+    #[test]
+    fn test_move_body_walker_over_ground() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let mut body = Simple(0);
+
+        map.add_entity(1, Layer::Ground, 99).unwrap();
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        assert!(move_body(
+            &mut map,
+            ENTITY,
+            &mut body,
+            MovementProfile::default(),
+            East
+        )
+        .is_moved());
+        assert_eq!(
+            map.get_entities(1),
+            vec![(Layer::Ground, 99), (Layer::Walker, ENTITY)]
+        );
+    }
+
+    #[test]
+    fn test_move_body_blocked_by_rule() {
+        let rules = BlockingRules::default().add(Layer::Walker, Layer::Flyer);
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_blocking_rules(rules)
+            .build();
+        let mut body = Simple(0);
+
+        map.add_entity(1, Layer::Flyer, 99).unwrap();
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                East
+            ),
+            MoveResult::BlockedByEntity(vec![99])
+        );
+        assert_eq!(body, Simple(0));
+    }
+
+    #[test]
+    fn test_check_move_with_mobility() {
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 1), Wall)
+            .build();
+        let body = Simple(1);
+        let mobility = Mobility::default();
+
+        map.add_entity(2, Layer::Walker, 99).unwrap();
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        let check = |dir| {
+            check_move(
+                &map,
+                ENTITY,
+                &body,
+                MovementProfile::new(Layer::Walker, mobility),
+                dir,
+            )
+        };
+
+        assert_eq!(check(West), None);
+        assert_eq!(check(East), Some(MoveResult::BlockedByEntity(vec![99])));
+        assert_eq!(check(North), Some(MoveResult::BlockedByTile(4)));
+        assert_eq!(check(South), Some(MoveResult::OutOfBounds));
+        assert_eq!(map.get_entity(1, Layer::Walker), Some(&ENTITY));
+    }
+
+    // teleport_body
+
+    #[test]
+    fn test_teleport_body() {
+        let mut map = TileMapBuilder::new(CORRIDOR_SIZE, Floor).build();
+        let mut body = Big(0, 2);
+
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        assert_eq!(
+            teleport_body(&mut map, ENTITY, &mut body, MovementProfile::default(), 1),
+            MoveResult::Moved {
+                from: 0,
+                to: 1,
+                entered: vec![2, 8],
+                left: vec![0, 6],
+                cost: 1,
+            }
+        );
+        assert_eq!(body, Big(1, 2));
+        assert_eq!(map.get_entity(0, Layer::Walker), None);
+        assert_eq!(map.get_entity(8, Layer::Walker), Some(&ENTITY));
+    }
+
+    #[test]
+    fn test_teleport_body_blocked() {
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(2, 2), Wall)
+            .build();
+        let mut body = Simple(0);
+
+        map.add_entity(4, Layer::Walker, 99).unwrap();
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        assert_eq!(
+            teleport_body(&mut map, ENTITY, &mut body, MovementProfile::default(), 4),
+            MoveResult::BlockedByEntity(vec![99])
+        );
+        assert_eq!(
+            teleport_body(&mut map, ENTITY, &mut body, MovementProfile::default(), 8),
+            MoveResult::BlockedByTile(8)
+        );
+        assert_eq!(
+            teleport_body(&mut map, ENTITY, &mut body, MovementProfile::default(), 9),
+            MoveResult::OutOfBounds
+        );
+        assert_eq!(body, Simple(0));
+        assert_eq!(map.get_entity(0, Layer::Walker), Some(&ENTITY));
+    }
+
+    #[test]
+    fn test_move_body_with_mobility() {
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), Wall)
+            .set_tile(xy(2, 0), Chasm)
+            .build();
+        let ghost = Mobility::new(&[MovementMode::Walk, MovementMode::Phase]);
+        let mut body = Simple(0);
+
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                East
+            ),
+            MoveResult::BlockedByTile(1)
+        );
+        assert!(move_body(
+            &mut map,
+            ENTITY,
+            &mut body,
+            MovementProfile::new(Layer::Walker, ghost),
+            East
+        )
+        .is_moved());
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::new(Layer::Walker, ghost),
+                East
+            ),
+            MoveResult::BlockedByTile(2)
+        );
+        assert_eq!(body, Simple(1));
+    }
+
+    #[test]
+    fn test_move_body_blocked_by_entities() {
+        let mut map = TileMapBuilder::new(CORRIDOR_SIZE, Floor).build();
+        let mut body = Big(0, 2);
+
+        map.add_entity(8, Layer::Walker, 30).unwrap();
+        map.add_entity(2, Layer::Walker, 20).unwrap();
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                East
+            ),
+            MoveResult::BlockedByEntity(vec![20, 30])
+        );
+        assert_eq!(get_position(&body), 0);
+    }
+
+    #[test]
+    fn test_move_body_shaped() {
+        let mut map = TileMapBuilder::new(CORRIDOR_SIZE, Floor).build();
+        let mut body = Shaped(0, Shape::rectangle(xy(3, 2)));
+
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                East
+            ),
+            MoveResult::Moved {
+                from: 0,
+                to: 1,
+                entered: vec![3, 9],
+                left: vec![0, 6],
+                cost: 1,
+            }
+        );
+        assert_eq!(get_position(&body), 1);
+        assert_eq!(map.get_entity(0, Layer::Walker), None);
+        assert_eq!(map.get_entity(3, Layer::Walker), Some(&ENTITY));
+        assert_eq!(map.get_entity(9, Layer::Walker), Some(&ENTITY));
+    }
+
+    #[test]
+    fn test_move_body_shaped_blocked() {
+        let mut map = TileMapBuilder::new(CORRIDOR_SIZE, Floor)
+            .set_tile(xy(3, 1), Wall)
+            .build();
+        let mut body = Shaped(0, Shape::rectangle(xy(3, 2)));
+
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        assert_eq!(
+            move_body(
+                &mut map,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                East
+            ),
+            MoveResult::BlockedByTile(9)
+        );
+        assert_eq!(get_position(&body), 0);
+        assert_eq!(map.get_entity(2, Layer::Walker), Some(&ENTITY));
+    }
+
+    #[test]
+    fn test_move_body_shaped_rotates() {
+        let mut map = TileMapBuilder::new(CORRIDOR_SIZE, Floor).build();
+        let golem = Shape::new(vec![(0, 0), (0, 1), (0, 2), (1, 0)]).rotating();
+        let mut body = Shaped(get_index(1, 1, CORRIDOR_SIZE), golem);
+
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        assert!(move_body(
+            &mut map,
+            ENTITY,
+            &mut body,
+            MovementProfile::default(),
+            East
+        )
+        .is_moved());
+        assert_eq!(
+            get_indices(&body, CORRIDOR_SIZE),
+            vec![
+                get_index(2, 1, CORRIDOR_SIZE),
+                get_index(3, 1, CORRIDOR_SIZE),
+                get_index(4, 1, CORRIDOR_SIZE),
+                get_index(2, 0, CORRIDOR_SIZE),
+            ]
+        );
+
+        for index in get_indices(&body, CORRIDOR_SIZE) {
+            assert_eq!(map.get_entity(index, Layer::Walker), Some(&ENTITY));
+        }
+
+        assert_eq!(
+            map.get_entity(get_index(1, 3, CORRIDOR_SIZE), Layer::Walker),
+            None
+        );
+    }
+
+    #[test]
+    fn test_add_shaped_outside() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let body = Shaped(2, Shape::rectangle(xy(2, 1)));
+
+        assert_eq!(
+            add_entity_to_map(&mut map, &body, ENTITY),
+            Err(MapError::OutOfBounds(2))
+        );
+    }
+
+    #[test]
+    fn test_can_occupy_shaped() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let body = Shaped(0, Shape::new(vec![(0, 0), (1, 1)]));
+
+        map.add_entity(8, Layer::Walker, 99).unwrap();
+
+        assert!(can_occupy(
+            &map,
+            ENTITY,
+            &body,
+            MovementProfile::default(),
+            0
+        ));
+        assert!(!can_occupy(
+            &map,
+            ENTITY,
+            &body,
+            MovementProfile::default(),
+            2
+        ));
+        assert!(!can_occupy(
+            &map,
+            ENTITY,
+            &body,
+            MovementProfile::default(),
+            4
+        ));
+    }
+
+    // remove_entity_from_map
+
+    #[test]
+    fn test_remove_entity_from_map() {
+        for body in [Simple(0), Big(4, 2), Snake(vec![1, 4, 7, 7])] {
+            let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+            add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+            remove_entity_from_map(&mut map, &body, ENTITY).unwrap();
+
+            for i in 0..9 {
+                assert_eq!(map.get_entity(i, Layer::Walker), None);
+            }
+        }
+    }
+
+    // change_level
+
+    #[test]
+    fn test_add_all_to_world() {
+        let mut ecs = ECS::new();
+        let mut world = create_world();
+
+        ecs.get_storage_mgr_mut().register::<Body>();
+        ecs.get_storage_mgr_mut().register::<Level>();
+        ecs.get_storage_mgr_mut().register::<Occupancy>();
+        ecs.create_entity().with(Simple(0)).with(Level { id: 1 });
+        ecs.create_entity().with(Simple(1));
+
+        add_all_to_world(&mut ecs, &mut world).unwrap();
+
+        assert_eq!(world.get_level(0).get_entity(0, Layer::Walker), None);
+        assert_eq!(world.get_level(0).get_entity(1, Layer::Walker), None);
+        assert_eq!(world.get_level(1).get_entity(0, Layer::Walker), Some(&0));
+    }
+
+    #[test]
+    fn test_change_level_simple() {
+        let mut world = create_world();
+        let mut body = Simple(4);
+        let mut level = Level { id: 0 };
+
+        add_entity_to_map(world.get_level_mut(0), &body, ENTITY).unwrap();
+
+        assert_eq!(
+            change_level(
+                &mut world,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                &mut level
+            ),
+            Ok(())
+        );
+        assert_eq!(body, Simple(0));
+        assert_eq!(level, Level { id: 1 });
+        assert_eq!(world.get_level(0).get_entity(4, Layer::Walker), None);
+        assert_eq!(
+            world.get_level(1).get_entity(0, Layer::Walker),
+            Some(&ENTITY)
+        );
+    }
+
+    #[test]
+    fn test_change_level_big() {
+        let mut world = create_world();
+        let mut body = Big(0, 2);
+        let mut level = Level { id: 1 };
+
+        add_entity_to_map(world.get_level_mut(1), &body, ENTITY).unwrap();
+
+        assert_eq!(
+            change_level(
+                &mut world,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                &mut level
+            ),
+            Ok(())
+        );
+        assert_eq!(body, Big(4, 2));
+        assert_eq!(level, Level { id: 0 });
+        assert_big_entity(world.get_level(0), ENTITY);
+        for i in 0..9 {
+            assert_eq!(world.get_level(1).get_entity(i, Layer::Walker), None);
+        }
+    }
+
+    #[test]
+    fn test_change_level_snake() {
+        let mut world = create_world();
+        let mut body = Snake(vec![4, 5, 8]);
+        let mut level = Level { id: 0 };
+
+        add_entity_to_map(world.get_level_mut(0), &body, ENTITY).unwrap();
+
+        assert_eq!(
+            change_level(
+                &mut world,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                &mut level
+            ),
+            Ok(())
+        );
+        assert_eq!(body, Snake(vec![0, 0, 0]));
+        assert_eq!(
+            world.get_level(1).get_entity(0, Layer::Walker),
+            Some(&ENTITY)
+        );
+        for i in 0..9 {
+            assert_eq!(world.get_level(0).get_entity(i, Layer::Walker), None);
+        }
+    }
+
+    #[test]
+    fn test_change_level_without_stairs() {
+        let mut world = create_world();
+        let mut body = Simple(3);
+        let mut level = Level { id: 0 };
+
+        add_entity_to_map(world.get_level_mut(0), &body, ENTITY).unwrap();
+
+        assert_eq!(
+            change_level(
+                &mut world,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                &mut level
+            ),
+            Err(MapError::WrongTile {
+                index: 3,
+                found: Floor,
+            })
+        );
+        assert_eq!(body, Simple(3));
+        assert_eq!(level, Level { id: 0 });
+    }
+
+    #[test]
+    fn test_change_level_blocked() {
+        let mut world = create_world();
+        let mut body = Simple(4);
+        let mut level = Level { id: 0 };
+
+        add_entity_to_map(world.get_level_mut(0), &body, ENTITY).unwrap();
+        world
+            .get_level_mut(1)
+            .add_entity(0, Layer::Walker, 99)
+            .unwrap();
+
+        assert_eq!(
+            change_level(
+                &mut world,
+                ENTITY,
+                &mut body,
+                MovementProfile::default(),
+                &mut level
+            ),
+            Err(MapError::Blocked { index: 0, by: 99 })
+        );
+        assert_eq!(body, Simple(4));
+        assert_eq!(
+            world.get_level(0).get_entity(4, Layer::Walker),
+            Some(&ENTITY)
+        );
+    }
+
+    fn create_world() -> World {
+        let upper = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 1), Stairs)
+            .build();
+        let lower = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(0, 0), Stairs)
+            .build();
+        let mut world = World::new(vec![upper, lower]);
+
+        world.connect(location(0, 4), location(1, 0));
+        world
+    }
+
+    // find_path_for_body
+
+    #[test]
+    fn test_find_path_for_simple() {
+        let map = create_corridor_map();
+        let body = Simple(get_index(0, 0, CORRIDOR_SIZE));
+
+        let path = find_path_for_body(
+            &map,
+            ENTITY,
+            &body,
+            MovementProfile::default(),
+            get_index(5, 0, CORRIDOR_SIZE),
+            100,
+        );
+
+        assert_eq!(path.map(|p| p.cost), Some(11));
+    }
+
+    #[test]
+    fn test_find_path_for_big_needs_wide_corridor() {
+        let map = create_corridor_map();
+        let body = Big(get_index(0, 0, CORRIDOR_SIZE), 2);
+
+        let path = find_path_for_body(
+            &map,
+            ENTITY,
+            &body,
+            MovementProfile::default(),
+            get_index(4, 0, CORRIDOR_SIZE),
+            100,
+        );
+
+        assert_eq!(path.map(|p| p.cost), Some(10));
+    }
+
+    #[test]
+    fn test_find_path_for_big_blocked() {
+        let map = create_corridor_map();
+        let body = Big(get_index(0, 0, CORRIDOR_SIZE), 3);
+
+        let path = find_path_for_body(
+            &map,
+            ENTITY,
+            &body,
+            MovementProfile::default(),
+            get_index(3, 0, CORRIDOR_SIZE),
+            100,
+        );
+
+        assert_eq!(path, None);
+    }
+
+    #[test]
+    fn test_find_path_for_snake_through_own_tail() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let body = Snake(vec![3, 0, 1, 2, 5]);
+
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        let path =
+            find_path_for_body(&map, ENTITY, &body, MovementProfile::default(), 2, 100).unwrap();
+
+        assert_eq!(path.indices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_find_path_blocked_by_other_entity() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        map.add_entity(1, Layer::Walker, 99).unwrap();
+        map.add_entity(3, Layer::Walker, 99).unwrap();
+
+        assert_eq!(
+            find_path_for_body(&map, ENTITY, &Simple(0), MovementProfile::default(), 2, 100),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_path_for_body_with_mobility() {
+        let map = create_corridor_map();
+        let body = Big(get_index(0, 0, CORRIDOR_SIZE), 2);
+        let ghost = Mobility::new(&[MovementMode::Walk, MovementMode::Phase]);
+        let goal = get_index(4, 0, CORRIDOR_SIZE);
+
+        let path = find_path_for_body(
+            &map,
+            ENTITY,
+            &body,
+            MovementProfile::new(Layer::Walker, ghost),
+            goal,
+            100,
+        );
+
+        assert_eq!(path.map(|p| p.cost), Some(4));
+    }
+
+    // find_spawn_location
+
+    #[test]
+    fn test_find_spawn_location() {
+        let mut map = create_corridor_map();
+        let body = Big(0, 2);
+        let mobility = Mobility::default();
+        let near = get_index(3, 3, CORRIDOR_SIZE);
+
+        map.add_entity(get_index(1, 4, CORRIDOR_SIZE), Layer::Walker, 99)
+            .unwrap();
+
+        let location = find_spawn_location(
+            &map,
+            ENTITY,
+            &body,
+            MovementProfile::new(Layer::Walker, mobility),
+            near,
+            5,
+        );
+
+        assert_eq!(location, Some(get_index(3, 3, CORRIDOR_SIZE)));
+        assert_eq!(
+            find_spawn_location(
+                &map,
+                ENTITY,
+                &body,
+                MovementProfile::new(Layer::Walker, mobility),
+                2,
+                1
+            ),
+            None
+        );
+        assert_eq!(
+            find_spawn_location(
+                &map,
+                ENTITY,
+                &body,
+                MovementProfile::new(Layer::Walker, mobility),
+                2,
+                2
+            ),
+            Some(0)
+        );
+    }
+
+    // get_reachable_for_body
+
+    #[test]
+    fn test_get_reachable_for_body() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), Wall)
+            .set_tile(xy(0, 1), Water)
+            .build();
+        let mobility = Mobility::default();
+
+        let reachable = get_reachable_for_body(
+            &map,
+            ENTITY,
+            &Simple(0),
+            MovementProfile::new(Layer::Walker, mobility),
+            3,
+        );
+
+        assert_eq!(reachable, vec![(0, 0), (3, 2), (4, 3), (6, 3)]);
+    }
+
+    #[test]
+    fn test_get_reachable_for_big_body() {
+        let mut map = create_corridor_map();
+        let body = Big(0, 2);
+        let mobility = Mobility::default();
+
+        map.add_entity(get_index(0, 4, CORRIDOR_SIZE), Layer::Walker, 99)
+            .unwrap();
+
+        let reachable = get_reachable_for_body(
+            &map,
+            ENTITY,
+            &body,
+            MovementProfile::new(Layer::Walker, mobility),
+            5,
+        );
+
+        assert_eq!(reachable, vec![(0, 0), (6, 1), (12, 2)]);
+    }
+
+    #[test]
+    fn test_get_reachable_for_body_square8() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_topology(Topology::Square8)
+            .set_tile(xy(1, 0), Wall)
+            .set_tile(xy(0, 1), Wall)
+            .build();
+
+        let reachable =
+            get_reachable_for_body(&map, ENTITY, &Simple(0), MovementProfile::default(), 1);
+
+        assert_eq!(reachable, vec![(0, 0), (4, 1)]);
+    }
+
+    #[test]
+    fn test_get_reachable_for_body_hex() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_topology(Topology::Hex)
+            .build();
+
+        let reachable =
+            get_reachable_for_body(&map, ENTITY, &Simple(4), MovementProfile::default(), 1);
+
+        assert_eq!(
+            reachable,
+            vec![(1, 1), (2, 1), (3, 1), (4, 0), (5, 1), (7, 1), (8, 1)]
+        );
+    }
+
+    // move_body_to
+
+    #[test]
+    fn test_move_body_to() {
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let mut body = Snake(vec![0, 1]);
+
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        assert_eq!(
+            check_move_to(&map, ENTITY, &body, MovementProfile::default(), 4),
+            Some(MoveResult::NotAdjacent(4))
+        );
+        assert!(
+            move_body_to(&mut map, ENTITY, &mut body, MovementProfile::default(), 3).is_moved()
+        );
+        assert_eq!(body, Snake(vec![3, 0]));
+        assert_eq!(map.get_entity(1, Layer::Walker), None);
+    }
+
+    #[test]
+    fn test_move_body_to_rotates() {
+        let mut map = TileMapBuilder::new(CORRIDOR_SIZE, Floor).build();
+        let golem = Shape::new(vec![(0, 0), (0, 1), (0, 2), (1, 0)]).rotating();
+        let mut body = Shaped(get_index(1, 1, CORRIDOR_SIZE), golem);
+
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        assert!(move_body_to(
+            &mut map,
+            ENTITY,
+            &mut body,
+            MovementProfile::default(),
+            get_index(2, 1, CORRIDOR_SIZE)
+        )
+        .is_moved());
+        assert_eq!(
+            get_indices(&body, CORRIDOR_SIZE),
+            vec![
+                get_index(2, 1, CORRIDOR_SIZE),
+                get_index(3, 1, CORRIDOR_SIZE),
+                get_index(4, 1, CORRIDOR_SIZE),
+                get_index(2, 0, CORRIDOR_SIZE),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_move_body_to_costs_step_cost() {
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(0, 0), Rubble)
+            .build();
+        let mut body = Snake(vec![1, 0, 0]);
+        let profile = MovementProfile::default();
+
+        add_entity_to_map(&mut map, &body, ENTITY).unwrap();
+
+        assert_eq!(get_step_cost(&map, ENTITY, &body, profile, 2), Some(1));
+        assert_eq!(
+            move_body_to(&mut map, ENTITY, &mut body, profile, 2),
+            MoveResult::Moved {
+                from: 1,
+                to: 2,
+                entered: vec![2],
+                left: vec![],
+                cost: 1,
+            }
+        );
+    }
+
+    // is_reachable_for_body
+
+    #[test]
+    fn test_is_reachable_for_body() {
+        let map = create_corridor_map();
+        let start = get_index(0, 0, CORRIDOR_SIZE);
+        let goal = get_index(4, 0, CORRIDOR_SIZE);
+
+        assert!(is_reachable_for_body(
+            &map,
+            ENTITY,
+            &Simple(start),
+            MovementProfile::default(),
+            goal
+        ));
+        assert!(is_reachable_for_body(
+            &map,
+            ENTITY,
+            &Big(start, 2),
+            MovementProfile::default(),
+            goal
+        ));
+        assert!(!is_reachable_for_body(
+            &map,
+            ENTITY,
+            &Big(start, 3),
+            MovementProfile::default(),
+            goal
+        ));
+        assert!(is_reachable_for_body(
+            &map,
+            ENTITY,
+            &Snake(vec![start; 3]),
+            MovementProfile::default(),
+            goal
+        ));
+    }
+
+    #[test]
+    fn test_is_reachable_for_body_with_mobility() {
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .add_rectangle(xy(1, 0), xy(1, 3), Chasm)
+            .build();
+        let dragon = Mobility::new(&[MovementMode::Walk, MovementMode::Fly]);
+
+        assert!(!is_reachable_for_body(
+            &map,
+            ENTITY,
+            &Simple(0),
+            MovementProfile::default(),
+            2
+        ));
+        assert!(is_reachable_for_body(
+            &map,
+            ENTITY,
+            &Simple(0),
+            MovementProfile::new(Layer::Flyer, dragon),
+            2
+        ));
+    }
+
+    #[test]
+    fn test_get_movement_profile() {
+        let mut ecs = ECS::new();
+        let dragon = Mobility::new(&[MovementMode::Fly]);
+
+        ecs.get_storage_mgr_mut().register::<Locomotion>();
+        ecs.get_storage_mgr_mut().register::<Occupancy>();
+
+        let walker = ecs.create_entity().get_entity();
+        let flyer = ecs
+            .create_entity()
+            .with(Locomotion { mobility: dragon })
+            .with(Occupancy {
+                layer: Layer::Flyer,
+            })
+            .get_entity();
+
+        assert_eq!(
+            get_movement_profile(&ecs, walker),
+            MovementProfile::default()
+        );
+        assert_eq!(
+            get_movement_profile(&ecs, flyer),
+            MovementProfile::new(Layer::Flyer, dragon)
+        );
+    }
+
+    fn create_corridor_map() -> TileMap {
+        TileMapBuilder::new(CORRIDOR_SIZE, Floor)
+            .add_rectangle(xy(2, 0), xy(2, 3), Wall)
+            .build()
+    }
+
     // asserts
 
     fn assert_simple(map: &mut TileMap) -> () {
-        assert_eq!(map.get_entity(0), Some(&ENTITY));
+        assert_eq!(map.get_entity(0, Layer::Walker), Some(&ENTITY));
         for i in 1..9 {
-            assert_eq!(map.get_entity(i), None);
+            assert_eq!(map.get_entity(i, Layer::Walker), None);
         }
     }
 
@@ -218,27 +1847,27 @@ mod tests {
         assert_big_entity(map, ENTITY);
     }
 
-    fn assert_big_entity(map: &mut TileMap, entity: usize) {
-        assert_eq!(map.get_entity(0), None);
-        assert_eq!(map.get_entity(1), None);
-        assert_eq!(map.get_entity(2), None);
-        assert_eq!(map.get_entity(3), None);
-        assert_eq!(map.get_entity(4), Some(&entity));
-        assert_eq!(map.get_entity(5), Some(&entity));
-        assert_eq!(map.get_entity(6), None);
-        assert_eq!(map.get_entity(7), Some(&entity));
-        assert_eq!(map.get_entity(8), Some(&entity));
+    fn assert_big_entity(map: &TileMap, entity: usize) {
+        assert_eq!(map.get_entity(0, Layer::Walker), None);
+        assert_eq!(map.get_entity(1, Layer::Walker), None);
+        assert_eq!(map.get_entity(2, Layer::Walker), None);
+        assert_eq!(map.get_entity(3, Layer::Walker), None);
+        assert_eq!(map.get_entity(4, Layer::Walker), Some(&entity));
+        assert_eq!(map.get_entity(5, Layer::Walker), Some(&entity));
+        assert_eq!(map.get_entity(6, Layer::Walker), None);
+        assert_eq!(map.get_entity(7, Layer::Walker), Some(&entity));
+        assert_eq!(map.get_entity(8, Layer::Walker), Some(&entity));
     }
 
     fn assert_snake(map: &mut TileMap) {
-        assert_eq!(map.get_entity(0), None);
-        assert_eq!(map.get_entity(1), Some(&ENTITY));
-        assert_eq!(map.get_entity(2), None);
-        assert_eq!(map.get_entity(3), None);
-        assert_eq!(map.get_entity(4), Some(&ENTITY));
-        assert_eq!(map.get_entity(5), None);
-        assert_eq!(map.get_entity(6), None);
-        assert_eq!(map.get_entity(7), Some(&ENTITY));
-        assert_eq!(map.get_entity(8), None);
+        assert_eq!(map.get_entity(0, Layer::Walker), None);
+        assert_eq!(map.get_entity(1, Layer::Walker), Some(&ENTITY));
+        assert_eq!(map.get_entity(2, Layer::Walker), None);
+        assert_eq!(map.get_entity(3, Layer::Walker), None);
+        assert_eq!(map.get_entity(4, Layer::Walker), Some(&ENTITY));
+        assert_eq!(map.get_entity(5, Layer::Walker), None);
+        assert_eq!(map.get_entity(6, Layer::Walker), None);
+        assert_eq!(map.get_entity(7, Layer::Walker), Some(&ENTITY));
+        assert_eq!(map.get_entity(8, Layer::Walker), None);
     }
 }
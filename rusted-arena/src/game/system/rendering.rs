@@ -1,13 +1,37 @@
-use crate::game::component::body::Body;
+use crate::game::component::body::{get_indices, Body};
 use crate::game::component::graphic::{Graphic, GraphicData};
+use crate::game::map::memory::TileMemory;
+use crate::game::map::TileMap;
 use crate::utils::ecs::storage::ComponentStorage;
 use crate::utils::ecs::ECS;
-use rusted_tiles::math::get_point;
-use rusted_tiles::math::point::Point;
 use rusted_tiles::rendering::tile::TileRenderer;
 use std::cmp::min;
 
-pub fn render_entities(ecs: &mut ECS, renderer: &mut dyn TileRenderer, size: Point) {
+pub fn render_entities(ecs: &mut ECS, renderer: &mut dyn TileRenderer, map: &TileMap) {
+    render_filtered_entities(ecs, renderer, map, |_| true);
+}
+
+pub fn render_visible_entities(
+    ecs: &mut ECS,
+    renderer: &mut dyn TileRenderer,
+    map: &TileMap,
+    memory: &TileMemory,
+) {
+    render_filtered_entities(ecs, renderer, map, |body| {
+        get_indices(body, map.get_size())
+            .iter()
+            .any(|index| memory.is_visible(*index))
+    });
+}
+
+fn render_filtered_entities<F>(
+    ecs: &mut ECS,
+    renderer: &mut dyn TileRenderer,
+    map: &TileMap,
+    filter: F,
+) where
+    F: Fn(&Body) -> bool,
+{
     let entities = ecs.get_entities_of_2::<Body, Graphic>();
     let body_storage = ecs.get_storage_mgr().get::<Body>();
     let graphic_storage = ecs.get_storage_mgr().get::<Graphic>();
@@ -15,43 +39,55 @@ pub fn render_entities(ecs: &mut ECS, renderer: &mut dyn TileRenderer, size: Poi
     for entity in entities {
         if let Some(body) = body_storage.get(entity) {
             if let Some(graphic) = graphic_storage.get(entity) {
-                render_entity(renderer, size, body, graphic);
+                if filter(body) {
+                    render_entity(renderer, map, body, graphic);
+                }
             }
         }
     }
 }
 
-fn render_entity(renderer: &mut dyn TileRenderer, size: Point, body: &Body, graphic: &Graphic) {
+fn render_entity(renderer: &mut dyn TileRenderer, map: &TileMap, body: &Body, graphic: &Graphic) {
     match body {
-        Body::Simple(index) => render_graphic(renderer, size, *index, graphic.get(0)),
-        Body::Big(index, s) => render_big_graphic(renderer, size, *index, *s, graphic.get(0)),
+        Body::Simple(index) => render_graphic(renderer, map, *index, graphic.get(0)),
+        Body::Big(index, s) => render_big_graphic(renderer, map, *index, *s, graphic.get(0)),
         Body::Snake(indices) => {
             for i in (0..indices.len()).rev() {
                 let data_index = min(i, 1);
-                render_graphic(renderer, size, indices[i], graphic.get(data_index))
+                render_graphic(renderer, map, indices[i], graphic.get(data_index))
+            }
+        }
+        Body::Shaped(..) => {
+            for index in get_indices(body, map.get_size()) {
+                render_graphic(renderer, map, index, graphic.get(0))
             }
         }
     }
 }
 
-fn render_graphic(renderer: &mut dyn TileRenderer, size: Point, index: usize, data: &GraphicData) {
+fn render_graphic(
+    renderer: &mut dyn TileRenderer,
+    map: &TileMap,
+    index: usize,
+    data: &GraphicData,
+) {
     match data {
         GraphicData::Ascii(ascii, color) => {
-            renderer.add_ascii(get_point(index, size), *ascii, *color)
+            renderer.add_ascii(map.get_render_position(index), *ascii, *color)
         }
     }
 }
 
 fn render_big_graphic(
     renderer: &mut dyn TileRenderer,
-    size: Point,
+    map: &TileMap,
     index: usize,
     tile_size: u32,
     data: &GraphicData,
 ) {
     match data {
         GraphicData::Ascii(ascii, color) => {
-            renderer.add_big_ascii(get_point(index, size), tile_size, *ascii, *color)
+            renderer.add_big_ascii(map.get_render_position(index), tile_size, *ascii, *color)
         }
     }
 }
@@ -0,0 +1,199 @@
+use crate::game::component::speed::{Speed, NORMAL_SPEED};
+use crate::game::system::movement::MoveResult;
+use crate::utils::ecs::storage::ComponentStorage;
+use crate::utils::ecs::ECS;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub const ACTION_COST: u64 = 100;
+
+#[derive(Clone, Debug, Default)]
+pub struct Scheduler {
+    time: u64,
+    sequence: u64,
+    queue: BinaryHeap<Reverse<(u64, u64, usize)>>,
+}
+
+impl Scheduler {
+    pub fn new() -> Scheduler {
+        Scheduler::default()
+    }
+
+    pub fn get_time(&self) -> u64 {
+        self.time
+    }
+
+    pub fn add(&mut self, entity: usize, delay: u64) {
+        self.queue
+            .push(Reverse((self.time + delay, self.sequence, entity)));
+        self.sequence += 1;
+    }
+
+    pub fn remove(&mut self, entity: usize) {
+        self.queue.retain(|Reverse((_, _, e))| *e != entity);
+    }
+
+    pub fn contains(&self, entity: usize) -> bool {
+        self.queue.iter().any(|Reverse((_, _, e))| *e == entity)
+    }
+
+    pub fn peek(&self) -> Option<usize> {
+        self.queue.peek().map(|Reverse((_, _, entity))| *entity)
+    }
+
+    pub fn next_turn(&mut self) -> Option<usize> {
+        let Reverse((time, _, entity)) = self.queue.pop()?;
+        self.time = time;
+        Some(entity)
+    }
+
+    pub fn end_turn(&mut self, entity: usize, cost: u64, speed: u32) {
+        self.add(entity, get_delay(cost, speed));
+    }
+}
+
+pub fn get_delay(cost: u64, speed: u32) -> u64 {
+    cost * NORMAL_SPEED as u64 / speed.max(1) as u64
+}
+
+pub fn get_move_cost(result: &MoveResult) -> u64 {
+    match result {
+        MoveResult::Moved { cost, .. } => ACTION_COST * *cost as u64,
+        _ => ACTION_COST,
+    }
+}
+
+pub fn get_speed(ecs: &ECS, entity: usize) -> u32 {
+    let speed_storage = ecs.get_storage_mgr().get::<Speed>();
+    speed_storage
+        .get(entity)
+        .map_or(NORMAL_SPEED, |speed| speed.value)
+}
+
+pub fn schedule_all(ecs: &ECS, scheduler: &mut Scheduler) {
+    let speed_storage = ecs.get_storage_mgr().get::<Speed>();
+
+    for &entity in ecs.get_entities() {
+        if let Some(speed) = speed_storage.get(entity) {
+            scheduler.add(entity, get_delay(ACTION_COST, speed.value));
+        }
+    }
+}
+
+pub fn end_turn(ecs: &ECS, scheduler: &mut Scheduler, entity: usize, cost: u64) {
+    scheduler.end_turn(entity, cost, get_speed(ecs, entity));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_ecs(speeds: &[u32]) -> ECS {
+        let mut ecs = ECS::new();
+
+        ecs.get_storage_mgr_mut().register::<Speed>();
+
+        for &value in speeds {
+            ecs.create_entity().with(Speed { value });
+        }
+
+        ecs
+    }
+
+    #[test]
+    fn test_get_delay() {
+        assert_eq!(get_delay(ACTION_COST, NORMAL_SPEED), 100);
+        assert_eq!(get_delay(ACTION_COST, 200), 50);
+        assert_eq!(get_delay(50, 50), 100);
+        assert_eq!(get_delay(ACTION_COST, 0), 10000);
+    }
+
+    #[test]
+    fn test_get_move_cost() {
+        let moved = |cost| MoveResult::Moved {
+            from: 0,
+            to: 1,
+            entered: vec![1],
+            left: vec![0],
+            cost,
+        };
+
+        assert_eq!(get_move_cost(&moved(1)), ACTION_COST);
+        assert_eq!(get_move_cost(&moved(2)), 2 * ACTION_COST);
+        assert_eq!(get_move_cost(&MoveResult::OutOfBounds), ACTION_COST);
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut scheduler = Scheduler::new();
+
+        assert_eq!(scheduler.next_turn(), None);
+        assert_eq!(scheduler.get_time(), 0);
+    }
+
+    #[test]
+    fn test_ties_are_first_come_first_served() {
+        let mut scheduler = Scheduler::new();
+
+        scheduler.add(3, 10);
+        scheduler.add(1, 10);
+        scheduler.add(2, 5);
+
+        assert_eq!(scheduler.next_turn(), Some(2));
+        assert_eq!(scheduler.get_time(), 5);
+        assert_eq!(scheduler.next_turn(), Some(3));
+        assert_eq!(scheduler.next_turn(), Some(1));
+        assert_eq!(scheduler.get_time(), 10);
+    }
+
+    #[test]
+    fn test_faster_entity_acts_more_often() {
+        let ecs = create_ecs(&[100, 200]);
+        let mut scheduler = Scheduler::new();
+        let mut turns = Vec::new();
+
+        schedule_all(&ecs, &mut scheduler);
+
+        for _ in 0..6 {
+            let entity = scheduler.next_turn().unwrap();
+            turns.push(entity);
+            end_turn(&ecs, &mut scheduler, entity, ACTION_COST);
+        }
+
+        assert_eq!(turns, vec![1, 0, 1, 1, 0, 1]);
+        assert_eq!(scheduler.get_time(), 200);
+    }
+
+    #[test]
+    fn test_expensive_action() {
+        let ecs = create_ecs(&[100, 100]);
+        let mut scheduler = Scheduler::new();
+
+        schedule_all(&ecs, &mut scheduler);
+
+        assert_eq!(scheduler.next_turn(), Some(0));
+        end_turn(&ecs, &mut scheduler, 0, 3 * ACTION_COST);
+
+        assert_eq!(scheduler.next_turn(), Some(1));
+        end_turn(&ecs, &mut scheduler, 1, ACTION_COST);
+        assert_eq!(scheduler.next_turn(), Some(1));
+        end_turn(&ecs, &mut scheduler, 1, ACTION_COST);
+        assert_eq!(scheduler.next_turn(), Some(1));
+        assert_eq!(scheduler.get_time(), 300);
+        assert_eq!(scheduler.peek(), Some(0));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut scheduler = Scheduler::new();
+
+        scheduler.add(0, 10);
+        scheduler.add(1, 20);
+        scheduler.remove(0);
+
+        assert!(!scheduler.contains(0));
+        assert!(scheduler.contains(1));
+        assert_eq!(scheduler.next_turn(), Some(1));
+        assert_eq!(scheduler.next_turn(), None);
+    }
+}
@@ -0,0 +1,236 @@
+use crate::game::component::body::*;
+use crate::game::component::faction::Faction;
+use crate::game::component::graphic::Graphic;
+use crate::game::component::health::Health;
+use crate::game::component::locomotion::Locomotion;
+use crate::game::component::occupancy::Occupancy;
+use crate::game::component::stats::Stats;
+use crate::game::map::{MapError, TileMap};
+use crate::game::system::movement::get_entity_layer;
+use crate::utils::ecs::component::Component;
+use crate::utils::ecs::storage::ComponentStorage;
+use crate::utils::ecs::ECS;
+
+pub fn grow_snake(ecs: &mut ECS, entity: usize, segments: usize) {
+    grow(ecs.unwrap_component_mut::<Body>(entity), segments);
+}
+
+pub fn shrink_snake(
+    ecs: &mut ECS,
+    map: &mut TileMap,
+    entity: usize,
+    segments: usize,
+) -> Result<Vec<usize>, MapError> {
+    let layer = get_entity_layer(ecs, entity);
+    let mut body = ecs.unwrap_component::<Body>(entity).clone();
+    let freed = shrink(&mut body, segments);
+
+    map.remove_entity_from_indices(&freed, layer, entity)?;
+    *ecs.unwrap_component_mut::<Body>(entity) = body;
+
+    Ok(freed)
+}
+
+pub fn sever_snake(
+    ecs: &mut ECS,
+    map: &mut TileMap,
+    entity: usize,
+    segment: usize,
+    keep_tail: bool,
+) -> Result<Option<usize>, MapError> {
+    let layer = get_entity_layer(ecs, entity);
+    let mut body = ecs.unwrap_component::<Body>(entity).clone();
+
+    let tail = match split(&mut body, segment) {
+        None => return Ok(None),
+        Some(tail) => tail,
+    };
+
+    let freed = get_freed_indices(
+        &get_indices(&body, map.get_size()),
+        &get_indices(&tail, map.get_size()),
+    );
+
+    map.remove_entity_from_indices(&freed, layer, entity)?;
+
+    let tail_entity = if keep_tail {
+        let tail_entity = ecs.create_entity().get_entity();
+        let tail_indices = get_indices(&tail, map.get_size());
+
+        if let Err(error) = map.add_entity_to_indices(&tail_indices, layer, tail_entity) {
+            ecs.remove_entity(tail_entity);
+            map.add_entity_to_indices(&freed, layer, entity)?;
+            return Err(error);
+        }
+
+        ecs.get_storage_mgr_mut()
+            .get_mut::<Body>()
+            .add(tail_entity, tail);
+
+        copy_component::<Occupancy>(ecs, entity, tail_entity);
+        copy_component::<Locomotion>(ecs, entity, tail_entity);
+        copy_component::<Faction>(ecs, entity, tail_entity);
+        copy_component::<Graphic>(ecs, entity, tail_entity);
+        copy_component::<Health>(ecs, entity, tail_entity);
+        copy_component::<Stats>(ecs, entity, tail_entity);
+
+        Some(tail_entity)
+    } else {
+        None
+    };
+
+    *ecs.unwrap_component_mut::<Body>(entity) = body;
+
+    Ok(tail_entity)
+}
+
+fn copy_component<C: Component + Clone>(ecs: &mut ECS, from: usize, to: usize) {
+    if !ecs.get_storage_mgr().is_registered::<C>() {
+        return;
+    }
+
+    if let Some(component) = ecs.get_storage_mgr().get::<C>().get(from).cloned() {
+        ecs.get_storage_mgr_mut().get_mut::<C>().add(to, component);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::component::body::Body::*;
+    use crate::game::fixture::create_ecs;
+    use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::layer::Layer;
+    use crate::game::map::mobility::Mobility;
+    use crate::game::map::Direction::*;
+    use crate::game::map::TileType::*;
+    use crate::game::system::movement::{add_all_to_map, move_body, MovementProfile};
+    use rusted_tiles::math::point::*;
+
+    const SIZE: Point = Point { x: 5, y: 1 };
+
+    fn create(body: Body) -> (ECS, TileMap, usize) {
+        let mut ecs = create_ecs();
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+
+        ecs.get_storage_mgr_mut().register::<Faction>();
+        ecs.get_storage_mgr_mut().register::<Occupancy>();
+
+        let entity = ecs
+            .create_entity()
+            .with(body)
+            .with(Faction { id: 3 })
+            .with(Health::default())
+            .with(Occupancy {
+                layer: Layer::Flyer,
+            })
+            .get_entity();
+
+        add_all_to_map(&mut ecs, &mut map).unwrap();
+
+        (ecs, map, entity)
+    }
+
+    fn get_entities(map: &TileMap) -> Vec<Option<usize>> {
+        (0..5)
+            .map(|i| map.get_entity(i, Layer::Flyer).copied())
+            .collect()
+    }
+
+    #[test]
+    fn test_grow_from_stacked_start() {
+        let (mut ecs, mut map, entity) = create(Snake(vec![0; 2]));
+
+        grow_snake(&mut ecs, entity, 2);
+
+        for _ in 0..4 {
+            let body = ecs.unwrap_component_mut::<Body>(entity);
+            assert!(move_body(
+                &mut map,
+                entity,
+                body,
+                MovementProfile::new(Layer::Flyer, Mobility::default()),
+                East
+            )
+            .is_moved());
+        }
+
+        assert_eq!(
+            ecs.unwrap_component::<Body>(entity),
+            &Snake(vec![4, 3, 2, 1])
+        );
+        assert_eq!(
+            get_entities(&map),
+            vec![None, Some(0), Some(0), Some(0), Some(0)]
+        );
+    }
+
+    #[test]
+    fn test_shrink_snake() {
+        let (mut ecs, mut map, entity) = create(Snake(vec![2, 1, 0, 0]));
+
+        assert_eq!(shrink_snake(&mut ecs, &mut map, entity, 1), Ok(Vec::new()));
+        assert_eq!(shrink_snake(&mut ecs, &mut map, entity, 2), Ok(vec![1, 0]));
+
+        assert_eq!(ecs.unwrap_component::<Body>(entity), &Snake(vec![2]));
+        assert_eq!(get_entities(&map), vec![None, None, Some(0), None, None]);
+    }
+
+    #[test]
+    fn test_sever_snake() {
+        let (mut ecs, mut map, entity) = create(Snake(vec![4, 3, 2, 1]));
+
+        assert_eq!(sever_snake(&mut ecs, &mut map, entity, 2, false), Ok(None));
+
+        assert_eq!(ecs.unwrap_component::<Body>(entity), &Snake(vec![4, 3]));
+        assert_eq!(get_entities(&map), vec![None, None, None, Some(0), Some(0)]);
+    }
+
+    #[test]
+    fn test_sever_snake_into_two() {
+        let (mut ecs, mut map, entity) = create(Snake(vec![4, 3, 2, 1]));
+
+        let tail = sever_snake(&mut ecs, &mut map, entity, 3, true)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(ecs.unwrap_component::<Body>(entity), &Snake(vec![4, 3, 2]));
+        assert_eq!(ecs.unwrap_component::<Body>(tail), &Snake(vec![1]));
+        assert_eq!(ecs.unwrap_component::<Occupancy>(tail).layer, Layer::Flyer);
+        assert_eq!(ecs.unwrap_component::<Faction>(tail).id, 3);
+        assert_eq!(ecs.unwrap_component::<Health>(tail), &Health::default());
+        assert_eq!(
+            get_entities(&map),
+            vec![None, Some(tail), Some(0), Some(0), Some(0)]
+        );
+    }
+
+    #[test]
+    fn test_sever_overlapping_snake() {
+        let (mut ecs, mut map, entity) = create(Snake(vec![1, 2, 1, 0]));
+
+        assert_eq!(
+            sever_snake(&mut ecs, &mut map, entity, 2, true),
+            Err(MapError::Blocked { index: 1, by: 0 })
+        );
+
+        assert_eq!(
+            ecs.unwrap_component::<Body>(entity),
+            &Snake(vec![1, 2, 1, 0])
+        );
+        assert_eq!(ecs.get_entities(), &[0]);
+        assert_eq!(
+            get_entities(&map),
+            vec![Some(0), Some(0), Some(0), None, None]
+        );
+    }
+
+    #[test]
+    fn test_sever_invalid_segment() {
+        let (mut ecs, mut map, entity) = create(Snake(vec![1, 0]));
+
+        assert_eq!(sever_snake(&mut ecs, &mut map, entity, 0, true), Ok(None));
+        assert_eq!(sever_snake(&mut ecs, &mut map, entity, 2, true), Ok(None));
+        assert_eq!(ecs.unwrap_component::<Body>(entity), &Snake(vec![1, 0]));
+    }
+}
@@ -0,0 +1,333 @@
+use crate::game::component::body::{get_indices, Body};
+use crate::game::component::trigger::{Trigger, TriggerEvent};
+use crate::game::map::layer::Layer;
+use crate::game::map::mobility::MovementMode;
+use crate::game::map::{TileMap, TileType};
+use crate::game::rpg::combat::damage::Damage;
+use crate::game::system::health::HealthSystem;
+use crate::game::system::movement::{MoveResult, MovementProfile};
+use crate::utils::ecs::storage::ComponentStorage;
+use crate::utils::ecs::ECS;
+
+pub const LAVA_DAMAGE: i32 = 6;
+
+pub fn get_tile_triggers(tile: TileType) -> Vec<Trigger> {
+    match tile {
+        TileType::Lava => vec![
+            Trigger {
+                event: TriggerEvent::Enter,
+                damage: Damage { rank: LAVA_DAMAGE },
+            },
+            Trigger {
+                event: TriggerEvent::Stay,
+                damage: Damage { rank: LAVA_DAMAGE },
+            },
+        ],
+        _ => Vec::new(),
+    }
+}
+
+fn is_airborne(profile: MovementProfile) -> bool {
+    profile.layer == Layer::Flyer || profile.mobility.can(MovementMode::Fly)
+}
+
+pub fn get_triggers(
+    ecs: &ECS,
+    map: &TileMap,
+    entity: usize,
+    profile: MovementProfile,
+    index: usize,
+    event: TriggerEvent,
+) -> Vec<Trigger> {
+    let trigger_storage = ecs.get_storage_mgr().get::<Trigger>();
+    let airborne = is_airborne(profile);
+    let mut triggers = Vec::new();
+
+    if !airborne {
+        triggers.extend(
+            get_tile_triggers(map.get_tile(index))
+                .into_iter()
+                .filter(|trigger| trigger.event == event),
+        );
+    }
+
+    for (layer, other) in map.get_entities(index) {
+        if other == entity || (airborne && layer == Layer::Ground) {
+            continue;
+        }
+
+        if let Some(trigger) = trigger_storage.get(other) {
+            if trigger.event == event {
+                triggers.push(trigger.clone());
+            }
+        }
+    }
+
+    triggers
+}
+
+pub struct TriggerSystem<'a> {
+    health: &'a HealthSystem<'a>,
+}
+
+impl<'a> TriggerSystem<'a> {
+    pub fn new(health: &'a HealthSystem<'a>) -> TriggerSystem<'a> {
+        TriggerSystem { health }
+    }
+
+    pub fn on_move(
+        &self,
+        ecs: &mut ECS,
+        map: &TileMap,
+        entity: usize,
+        profile: MovementProfile,
+        result: &MoveResult,
+    ) -> Vec<Trigger> {
+        let (entered, left) = match result {
+            MoveResult::Moved { entered, left, .. } => (entered, left),
+            _ => return Vec::new(),
+        };
+        let mut triggers = Vec::new();
+
+        for &index in left {
+            triggers.extend(get_triggers(
+                ecs,
+                map,
+                entity,
+                profile,
+                index,
+                TriggerEvent::Leave,
+            ));
+        }
+
+        for &index in entered {
+            triggers.extend(get_triggers(
+                ecs,
+                map,
+                entity,
+                profile,
+                index,
+                TriggerEvent::Enter,
+            ));
+        }
+
+        self.apply(ecs, entity, triggers)
+    }
+
+    pub fn on_stay(
+        &self,
+        ecs: &mut ECS,
+        map: &TileMap,
+        entity: usize,
+        profile: MovementProfile,
+        body: &Body,
+    ) -> Vec<Trigger> {
+        let mut triggers = Vec::new();
+
+        for index in get_indices(body, map.get_size()) {
+            triggers.extend(get_triggers(
+                ecs,
+                map,
+                entity,
+                profile,
+                index,
+                TriggerEvent::Stay,
+            ));
+        }
+
+        self.apply(ecs, entity, triggers)
+    }
+
+    fn apply(&self, ecs: &mut ECS, entity: usize, triggers: Vec<Trigger>) -> Vec<Trigger> {
+        if !self.health.can_take_damage(ecs, entity) {
+            return Vec::new();
+        }
+
+        for trigger in &triggers {
+            self.health.take_damage(ecs, entity, &trigger.damage);
+        }
+
+        triggers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::component::body::Body::*;
+    use crate::game::component::health::Health;
+    use crate::game::component::health::HealthState::*;
+    use crate::game::fixture::{self, add_health, create_checker, create_toughness};
+    use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::mobility::Mobility;
+    use crate::game::map::Direction::*;
+    use crate::game::map::TileType::*;
+    use crate::game::rpg::character::skill::Skill;
+    use crate::game::system::movement::{add_entity_to_map, move_body, MovementProfile};
+    use rusted_tiles::math::point::*;
+
+    const SIZE: Point = Point { x: 3, y: 3 };
+
+    fn create_ecs(toughness: &Skill) -> (ECS, usize) {
+        let mut ecs = fixture::create_ecs();
+
+        ecs.get_storage_mgr_mut().register::<Trigger>();
+
+        let entity = ecs.create_entity().get_entity();
+        add_health(&mut ecs, entity, toughness);
+
+        (ecs, entity)
+    }
+
+    #[test]
+    fn test_get_tile_triggers() {
+        let events: Vec<TriggerEvent> = get_tile_triggers(Lava)
+            .iter()
+            .map(|trigger| trigger.event)
+            .collect();
+
+        assert_eq!(events, vec![TriggerEvent::Enter, TriggerEvent::Stay]);
+        assert_eq!(get_tile_triggers(Floor), Vec::new());
+        assert_eq!(get_tile_triggers(Water), Vec::new());
+    }
+
+    #[test]
+    fn test_enter_lava() {
+        let toughness = create_toughness();
+        let checker = create_checker(1);
+        let health = HealthSystem::new(&checker, &toughness);
+        let system = TriggerSystem::new(&health);
+        let (mut ecs, entity) = create_ecs(&toughness);
+        let mut map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(1, 0), Lava)
+            .build();
+        let mut body = Simple(0);
+
+        add_entity_to_map(&mut map, &body, entity).unwrap();
+        let result = move_body(
+            &mut map,
+            entity,
+            &mut body,
+            MovementProfile::default(),
+            East,
+        );
+
+        assert_eq!(
+            system.on_move(&mut ecs, &map, entity, MovementProfile::default(), &result),
+            get_triggers(
+                &ecs,
+                &map,
+                entity,
+                MovementProfile::default(),
+                1,
+                TriggerEvent::Enter
+            )
+        );
+        assert_eq!(ecs.unwrap_component::<Health>(entity).state, Reeling);
+    }
+
+    #[test]
+    fn test_stay_in_lava() {
+        let toughness = create_toughness();
+        let checker = create_checker(2);
+        let health = HealthSystem::new(&checker, &toughness);
+        let system = TriggerSystem::new(&health);
+        let (mut ecs, entity) = create_ecs(&toughness);
+        let map = TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(0, 0), Lava)
+            .set_tile(xy(1, 1), Lava)
+            .build();
+
+        assert_eq!(
+            system
+                .on_stay(
+                    &mut ecs,
+                    &map,
+                    entity,
+                    MovementProfile::default(),
+                    &Big(0, 2)
+                )
+                .len(),
+            2
+        );
+
+        assert_eq!(ecs.unwrap_component::<Health>(entity).state, Dead);
+    }
+
+    #[test]
+    fn test_fly_over_lava() {
+        let toughness = create_toughness();
+        let checker = create_checker(0);
+        let health = HealthSystem::new(&checker, &toughness);
+        let system = TriggerSystem::new(&health);
+        let (mut ecs, entity) = create_ecs(&toughness);
+        let map = TileMapBuilder::new(SIZE, Lava).build();
+        let flyer = MovementProfile::new(Layer::Flyer, Mobility::default());
+        let dragon = MovementProfile::new(Layer::Walker, Mobility::new(&[MovementMode::Fly]));
+
+        assert_eq!(
+            system.on_stay(&mut ecs, &map, entity, flyer, &Simple(4)),
+            Vec::new()
+        );
+        assert_eq!(
+            system.on_stay(&mut ecs, &map, entity, dragon, &Simple(4)),
+            Vec::new()
+        );
+        assert_eq!(ecs.unwrap_component::<Health>(entity).state, Healthy);
+    }
+
+    #[test]
+    fn test_leave_trap() {
+        let toughness = create_toughness();
+        let checker = create_checker(1);
+        let health = HealthSystem::new(&checker, &toughness);
+        let system = TriggerSystem::new(&health);
+        let (mut ecs, entity) = create_ecs(&toughness);
+        let trap = ecs
+            .create_entity()
+            .with(Trigger {
+                event: TriggerEvent::Leave,
+                damage: Damage { rank: 3 },
+            })
+            .get_entity();
+        let mut map = TileMapBuilder::new(SIZE, Floor).build();
+        let mut body = Simple(0);
+
+        map.add_entity(0, Layer::Ground, trap).unwrap();
+        map.add_entity(1, Layer::Ground, trap).unwrap();
+        add_entity_to_map(&mut map, &body, entity).unwrap();
+        let result = move_body(
+            &mut map,
+            entity,
+            &mut body,
+            MovementProfile::default(),
+            East,
+        );
+
+        system.on_move(&mut ecs, &map, entity, MovementProfile::default(), &result);
+
+        assert_eq!(ecs.unwrap_component::<Health>(entity).state, Reeling);
+    }
+
+    #[test]
+    fn test_ignore_without_health() {
+        let toughness = create_toughness();
+        let checker = create_checker(0);
+        let health = HealthSystem::new(&checker, &toughness);
+        let system = TriggerSystem::new(&health);
+        let (mut ecs, _) = create_ecs(&toughness);
+        let entity = ecs.create_entity().get_entity();
+        let map = TileMapBuilder::new(SIZE, Lava).build();
+
+        assert_eq!(
+            system.on_stay(
+                &mut ecs,
+                &map,
+                entity,
+                MovementProfile::default(),
+                &Simple(4)
+            ),
+            Vec::new()
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use crate::game::component::body::{get_indices, Body};
+use crate::game::map::fov::calculate_fov;
+use crate::game::map::line::*;
+use crate::game::map::TileMap;
+use std::collections::HashSet;
+
+pub fn calculate_fov_of_body(map: &TileMap, body: &Body, radius: u32) -> HashSet<usize> {
+    match body {
+        Body::Simple(index) => calculate_fov(map, *index, radius),
+        Body::Big(..) | Body::Shaped(..) => {
+            let mut visible = HashSet::new();
+
+            for index in get_indices(body, map.get_size()) {
+                visible.extend(calculate_fov(map, index, radius));
+            }
+
+            visible
+        }
+        Body::Snake(indices) => calculate_fov(map, indices[0], radius),
+    }
+}
+
+pub fn check_line_of_sight(
+    map: &TileMap,
+    source: &Body,
+    target: &Body,
+    mode: LineMode,
+) -> LineResult {
+    check_bodies(map, source, target, |from, to| {
+        check_sight(map, from, to, mode)
+    })
+}
+
+pub fn check_line_of_fire(
+    map: &TileMap,
+    source_entity: usize,
+    source: &Body,
+    target_entity: usize,
+    target: &Body,
+    mode: LineMode,
+) -> LineResult {
+    let ignored = [source_entity, target_entity];
+    check_bodies(map, source, target, |from, to| {
+        check_fire(map, from, to, mode, &ignored)
+    })
+}
+
+fn check_bodies<F>(map: &TileMap, source: &Body, target: &Body, check: F) -> LineResult
+where
+    F: Fn(usize, usize) -> LineResult,
+{
+    let size = map.get_size();
+    let mut first_result = None;
+
+    for from in get_indices(source, size) {
+        for to in get_indices(target, size) {
+            let result = check(from, to);
+
+            if result == LineResult::Clear {
+                return result;
+            }
+
+            first_result.get_or_insert(result);
+        }
+    }
+
+    first_result.unwrap_or(LineResult::Clear)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::component::body::Body::*;
+    use crate::game::map::builder::TileMapBuilder;
+    use crate::game::map::layer::Layer;
+    use crate::game::map::line::LineMode::*;
+    use crate::game::map::line::LineResult::*;
+    use crate::game::map::TileType::*;
+    use rusted_tiles::math::point::*;
+
+    const SIZE: Point = Point { x: 5, y: 3 };
+
+    fn create_map() -> TileMap {
+        TileMapBuilder::new(SIZE, Floor)
+            .set_tile(xy(2, 0), Wall)
+            .set_tile(xy(2, 1), Wall)
+            .build()
+    }
+
+    #[test]
+    fn test_simple() {
+        let map = create_map();
+
+        let visible = calculate_fov_of_body(&map, &Simple(0), 10);
+
+        assert!(visible.contains(&12));
+        assert!(!visible.contains(&13));
+    }
+
+    #[test]
+    fn test_big() {
+        let map = create_map();
+
+        let visible = calculate_fov_of_body(&map, &Big(5, 2), 10);
+
+        assert!(visible.contains(&13));
+        assert!(visible.contains(&14));
+    }
+
+    #[test]
+    fn test_snake_sees_from_head() {
+        let map = create_map();
+
+        let visible = calculate_fov_of_body(&map, &Snake(vec![0, 10, 11]), 10);
+
+        assert!(!visible.contains(&13));
+        assert!(!visible.contains(&14));
+    }
+
+    // line of sight
+
+    #[test]
+    fn test_line_of_sight_simple() {
+        let map = create_map();
+
+        assert_eq!(
+            check_line_of_sight(&map, &Simple(0), &Simple(3), Bresenham),
+            BlockedByTile(2)
+        );
+        assert_eq!(
+            check_line_of_sight(&map, &Simple(10), &Simple(14), Bresenham),
+            Clear
+        );
+    }
+
+    #[test]
+    fn test_line_of_sight_big_source() {
+        let map = create_map();
+
+        assert_eq!(
+            check_line_of_sight(&map, &Big(0, 2), &Simple(9), Bresenham),
+            BlockedByTile(7)
+        );
+        assert_eq!(
+            check_line_of_sight(&map, &Big(5, 2), &Simple(9), Bresenham),
+            Clear
+        );
+    }
+
+    #[test]
+    fn test_line_of_sight_snake_target() {
+        let map = create_map();
+
+        assert_eq!(
+            check_line_of_sight(&map, &Simple(10), &Snake(vec![4, 9]), Bresenham),
+            BlockedByTile(7)
+        );
+        assert_eq!(
+            check_line_of_sight(&map, &Simple(10), &Snake(vec![4, 9, 14]), Bresenham),
+            Clear
+        );
+    }
+
+    // line of fire
+
+    #[test]
+    fn test_line_of_fire_blocked_by_entity() {
+        let mut map = create_map();
+
+        map.add_entity(12, Layer::Walker, 7).unwrap();
+
+        assert_eq!(
+            check_line_of_fire(&map, 1, &Simple(10), 2, &Simple(14), Bresenham),
+            BlockedByEntity(7)
+        );
+    }
+
+    #[test]
+    fn test_line_of_fire_ignores_source_and_target() {
+        let mut map = create_map();
+        let source = Big(10, 1);
+        let target = Snake(vec![14, 13]);
+
+        map.add_entity(10, Layer::Walker, 1).unwrap();
+        map.add_entity(13, Layer::Walker, 2).unwrap();
+        map.add_entity(14, Layer::Walker, 2).unwrap();
+
+        assert_eq!(
+            check_line_of_fire(&map, 1, &source, 2, &target, Bresenham),
+            Clear
+        );
+    }
+}
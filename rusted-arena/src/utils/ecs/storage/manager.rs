@@ -29,6 +29,10 @@ impl StorageMgr {
         self.storage_map.insert(type_id, Box::new(new_storage));
     }
 
+    pub fn is_registered<C: Component>(&self) -> bool {
+        self.storage_map.contains_key(&TypeId::of::<C>())
+    }
+
     pub fn get<C: Component>(&self) -> &<C as Component>::Storage {
         let type_id = TypeId::of::<C>();
 
@@ -80,6 +84,17 @@ mod tests {
         assert_eq!(storage.get(0), None);
     }
 
+    #[test]
+    fn test_is_registered() {
+        let mut manager = StorageMgr::new();
+
+        assert!(!manager.is_registered::<ComponentA>());
+
+        manager.register::<ComponentA>();
+
+        assert!(manager.is_registered::<ComponentA>());
+    }
+
     #[test]
     #[should_panic(expected = "Component of type 'A' is already registered!")]
     fn test_register_twice() {
@@ -30,6 +30,11 @@ pub const CYAN: Color = Color {
     g: 1.0,
     b: 1.0,
 };
+pub const GRAY: Color = Color {
+    r: 0.5,
+    g: 0.5,
+    b: 0.5,
+};
 pub const GREEN: Color = Color {
     r: 0.0,
     g: 1.0,